@@ -0,0 +1,170 @@
+//! A derive macro for bytestring newtypes, to be used via the `derive` feature
+//! of [`serde-encoded-bytes`](https://docs.rs/serde-encoded-bytes).
+
+#![warn(
+    clippy::mod_module_files,
+    clippy::unwrap_used,
+    clippy::indexing_slicing,
+    missing_docs,
+    rust_2018_idioms,
+    trivial_casts,
+    trivial_numeric_casts,
+    unused_qualifications
+)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, Ident, Member, Type};
+
+/// Derives `Serialize` and `Deserialize` for a struct with a single bytestring field,
+/// delegating to one of the containers of `serde-encoded-bytes`.
+///
+/// The encoding and the container are set by the `encoded_bytes` attribute:
+/// ```ignore
+/// #[derive(EncodedBytes)]
+/// #[encoded_bytes(encoding = Hex, container = array)]
+/// struct PublicKey([u8; 32]);
+/// ```
+///
+/// The possible containers are
+/// `array` ([`ArrayLike`](https://docs.rs/serde-encoded-bytes/latest/serde_encoded_bytes/struct.ArrayLike.html)),
+/// `array_tuple` (`ArrayTupleLike`),
+/// `slice` (`SliceLike`),
+/// `borrowed_slice` (`BorrowedSliceLike`),
+/// and `boxed_array` (`BoxedArrayLike`).
+#[proc_macro_derive(EncodedBytes, attributes(encoded_bytes))]
+pub fn derive_encoded_bytes(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+const CONTAINERS: [(&str, &str); 5] = [
+    ("array", "ArrayLike"),
+    ("array_tuple", "ArrayTupleLike"),
+    ("slice", "SliceLike"),
+    ("borrowed_slice", "BorrowedSliceLike"),
+    ("boxed_array", "BoxedArrayLike"),
+];
+
+struct Options {
+    encoding: Type,
+    container: Ident,
+}
+
+fn parse_options(input: &DeriveInput) -> syn::Result<Options> {
+    let mut encoding = None;
+    let mut container = None;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("encoded_bytes"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("encoding") {
+                encoding = Some(meta.value()?.parse::<Type>()?);
+                Ok(())
+            } else if meta.path.is_ident("container") {
+                let name = meta.value()?.parse::<Ident>()?;
+                let type_name = CONTAINERS
+                    .iter()
+                    .find(|(attr_name, _)| name == attr_name)
+                    .map(|(_, type_name)| type_name)
+                    .ok_or_else(|| {
+                        let names = CONTAINERS
+                            .iter()
+                            .map(|(attr_name, _)| format!("`{attr_name}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        syn::Error::new(
+                            name.span(),
+                            format!("Unknown container `{name}`, expected one of {names}"),
+                        )
+                    })?;
+                container = Some(Ident::new(type_name, name.span()));
+                Ok(())
+            } else {
+                Err(meta.error("Unknown `encoded_bytes` parameter"))
+            }
+        })?;
+    }
+
+    let missing = |name: &str| {
+        syn::Error::new(
+            Span::call_site(),
+            format!("Missing `#[encoded_bytes({name} = ...)]` attribute"),
+        )
+    };
+
+    Ok(Options {
+        encoding: encoding.ok_or_else(|| missing("encoding"))?,
+        container: container.ok_or_else(|| missing("container"))?,
+    })
+}
+
+fn single_field(input: &DeriveInput) -> syn::Result<Member> {
+    let error = || {
+        syn::Error::new_spanned(
+            &input.ident,
+            "`EncodedBytes` can only be derived for structs with a single field",
+        )
+    };
+
+    let Data::Struct(data) = &input.data else {
+        return Err(error());
+    };
+    let mut fields = match &data.fields {
+        Fields::Named(fields) => fields.named.iter(),
+        Fields::Unnamed(fields) => fields.unnamed.iter(),
+        Fields::Unit => return Err(error()),
+    };
+    match (fields.next(), fields.next()) {
+        (Some(field), None) => Ok(field
+            .ident
+            .clone()
+            .map_or_else(|| Member::from(0), Member::Named)),
+        _ => Err(error()),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Options {
+        encoding,
+        container,
+    } = parse_options(input)?;
+    let member = single_field(input)?;
+
+    let krate = quote!(::serde_encoded_bytes);
+    let serde = quote!(#krate::__private::serde);
+    let ident = &input.ident;
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut de_generics = input.generics.clone();
+    de_generics.params.insert(0, parse_quote!('de));
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #serde::Serialize for #ident #ty_generics #where_clause {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: #serde::Serializer,
+            {
+                #krate::#container::<#encoding>::serialize(&self.#member, serializer)
+            }
+        }
+
+        impl #de_impl_generics #serde::Deserialize<'de> for #ident #ty_generics #where_clause {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: #serde::Deserializer<'de>,
+            {
+                #krate::#container::<#encoding>::deserialize(deserializer)
+                    .map(|value| Self { #member: value })
+            }
+        }
+    })
+}
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use serde_encoded_bytes::{EncodedBytes, Hex};
+
+#[derive(Debug, PartialEq, Eq, EncodedBytes)]
+#[encoded_bytes(encoding = Hex, container = array)]
+struct PublicKey([u8; 4]);
+
+#[derive(Debug, PartialEq, Eq, EncodedBytes)]
+#[encoded_bytes(encoding = Hex, container = slice)]
+struct Signature {
+    bytes: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Eq, EncodedBytes)]
+#[encoded_bytes(encoding = Hex, container = array_tuple)]
+struct Hash<T: AsRef<[u8]> + From<[u8; 2]>>(T);
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Message {
+    key: PublicKey,
+    signature: Signature,
+    hash: Hash<[u8; 2]>,
+}
+
+#[test]
+fn roundtrip() {
+    let val = Message {
+        key: PublicKey([1, 2, 3, 4]),
+        signature: Signature {
+            bytes: vec![5, 6, 7],
+        },
+        hash: Hash([8, 9]),
+    };
+
+    let val_str = serde_json::to_string(&val).unwrap();
+    assert_eq!(
+        val_str,
+        "{\"key\":\"0x01020304\",\"signature\":\"0x050607\",\"hash\":\"0x0809\"}"
+    );
+    assert_eq!(serde_json::from_str::<Message>(&val_str).unwrap(), val);
+
+    let val_bytes = rmp_serde::to_vec(&val).unwrap();
+    assert_eq!(
+        val_bytes,
+        [0x93, 0xc4, 4, 1, 2, 3, 4, 0xc4, 3, 5, 6, 7, 0x92, 8, 9]
+    );
+    assert_eq!(rmp_serde::from_slice::<Message>(&val_bytes).unwrap(), val);
+}
+
+#[test]
+fn errors() {
+    assert_eq!(
+        serde_json::from_str::<PublicKey>("\"0x010203\"")
+            .unwrap_err()
+            .to_string(),
+        "Expected a bytestring of length 4, got 3 at line 1 column 10"
+    );
+}
@@ -0,0 +1,376 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::cmp::min;
+use core::{fmt, marker::PhantomData};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::containers::Container;
+
+/// A `Serialize` adapter serializing an object with the given container.
+struct SerializeWith<'a, C, T>(&'a T, PhantomData<C>);
+
+impl<C: Container<T>, T> Serialize for SerializeWith<'_, C, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        C::serialize(self.0, serializer)
+    }
+}
+
+/// A `DeserializeSeed` deserializing an object with the given container.
+struct DeserializeWith<C, T>(PhantomData<(C, T)>);
+
+impl<C, T> Default for DeserializeWith<C, T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<'de, C: Container<T>, T> de::DeserializeSeed<'de> for DeserializeWith<C, T> {
+    type Value = T;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+        C::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+struct SeqVisitor<C, T>(PhantomData<(C, T)>);
+
+#[cfg(feature = "alloc")]
+impl<'de, C: Container<T>, T> de::Visitor<'de> for SeqVisitor<C, T> {
+    type Value = Vec<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a sequence of bytestrings")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        // The size hint comes from the input, so it cannot be fully trusted.
+        let mut result = Vec::with_capacity(min(seq.size_hint().unwrap_or(0), 4096));
+        while let Some(elem) = seq.next_element_seed(DeserializeWith::<C, T>(PhantomData))? {
+            result.push(elem);
+        }
+        Ok(result)
+    }
+}
+
+/// An adapter for sequences of bytestrings, e.g. `Vec<[u8; N]>` or `Vec<Vec<u8>>`,
+/// serializing each element with the container `C` (e.g. [`ArrayLike`](`crate::ArrayLike`)).
+///
+/// For use in the `#[serde(with)]` field attribute.
+///
+/// The elements are checked by the container on deserialization,
+/// so, for example, a wrong length of an array element is an error.
+///
+/// Requirements:
+/// - serializer requires the field to implement `AsRef<[T]>` and `C` to implement [`Container<T>`];
+/// - deserializer requires the field to implement `From<Vec<T>>`,
+///   `C` to implement [`Container<T>`], and the `alloc` feature.
+pub struct SeqOf<C>(PhantomData<C>);
+
+impl<C> SeqOf<C> {
+    /// Serializes a sequence of bytestrings.
+    pub fn serialize<T, Seq, S>(obj: &Seq, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        C: Container<T>,
+        Seq: AsRef<[T]>,
+        S: Serializer,
+    {
+        serializer.collect_seq(
+            obj.as_ref()
+                .iter()
+                .map(|elem| SerializeWith::<C, T>(elem, PhantomData)),
+        )
+    }
+
+    /// Deserializes a sequence of bytestrings.
+    #[cfg(feature = "alloc")]
+    pub fn deserialize<'de, T, Seq, D>(deserializer: D) -> Result<Seq, D::Error>
+    where
+        C: Container<T>,
+        Seq: From<Vec<T>>,
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_seq(SeqVisitor::<C, T>(PhantomData))
+            .map(Seq::from)
+    }
+}
+
+fn serialize_map<'a, KC, K, V, VW, M, S>(
+    obj: &'a M,
+    serializer: S,
+    wrap_value: impl Fn(&'a V) -> VW,
+) -> Result<S::Ok, S::Error>
+where
+    KC: Container<K>,
+    K: 'a,
+    V: 'a,
+    VW: Serialize,
+    &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+    S: Serializer,
+{
+    serializer.collect_map(
+        obj.into_iter()
+            .map(|(key, value)| (SerializeWith::<KC, K>(key, PhantomData), wrap_value(value))),
+    )
+}
+
+struct MapVisitor<KC, K, VS, M>(PhantomData<(KC, K, VS, M)>);
+
+impl<'de, KC, K, VS, M> de::Visitor<'de> for MapVisitor<KC, K, VS, M>
+where
+    KC: Container<K>,
+    VS: de::DeserializeSeed<'de> + Default,
+    M: Default + Extend<(K, VS::Value)>,
+{
+    type Value = M;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a map with bytestring keys")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let mut result = M::default();
+        while let Some(entry) =
+            map.next_entry_seed(DeserializeWith::<KC, K>(PhantomData), VS::default())?
+        {
+            result.extend(Some(entry));
+        }
+        Ok(result)
+    }
+}
+
+/// An adapter for maps with bytestring keys, e.g. `BTreeMap<[u8; N], V>`,
+/// serializing each key with the container `KC` (e.g. [`ArrayLike`](`crate::ArrayLike`)).
+///
+/// For use in the `#[serde(with)]` field attribute.
+///
+/// In human-readable formats the keys are serialized as encoded strings,
+/// so this can be used with formats requiring string keys, like JSON.
+/// The values are serialized as usual; use [`MapKeysValues`] if they are bytestrings as well.
+///
+/// Requirements:
+/// - serializer requires `&'a M` to implement `IntoIterator<Item = (&'a K, &'a V)>`,
+///   `KC` to implement [`Container<K>`], and `V` to implement `Serialize`;
+/// - deserializer requires the field to implement `Default + Extend<(K, V)>`,
+///   `KC` to implement [`Container<K>`], and `V` to implement `Deserialize`.
+pub struct MapKeys<KC>(PhantomData<KC>);
+
+impl<KC> MapKeys<KC> {
+    /// Serializes a map with bytestring keys.
+    pub fn serialize<'a, K, V, M, S>(obj: &'a M, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        KC: Container<K>,
+        K: 'a,
+        V: 'a + Serialize,
+        &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+        S: Serializer,
+    {
+        serialize_map::<KC, _, _, _, _, _>(obj, serializer, |value| value)
+    }
+
+    /// Deserializes a map with bytestring keys.
+    pub fn deserialize<'de, K, V, M, D>(deserializer: D) -> Result<M, D::Error>
+    where
+        KC: Container<K>,
+        V: Deserialize<'de>,
+        M: Default + Extend<(K, V)>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(MapVisitor::<KC, K, PhantomData<V>, M>(PhantomData))
+    }
+}
+
+/// An adapter for maps with bytestring keys and values, e.g. `BTreeMap<[u8; N], Vec<u8>>`,
+/// serializing each key with the container `KC` and each value with the container `VC`.
+///
+/// For use in the `#[serde(with)]` field attribute.
+///
+/// See [`MapKeys`] for details.
+///
+/// Requirements:
+/// - serializer requires `&'a M` to implement `IntoIterator<Item = (&'a K, &'a V)>`,
+///   `KC` to implement [`Container<K>`], and `VC` to implement [`Container<V>`];
+/// - deserializer requires the field to implement `Default + Extend<(K, V)>`,
+///   `KC` to implement [`Container<K>`], and `VC` to implement [`Container<V>`].
+pub struct MapKeysValues<KC, VC>(PhantomData<(KC, VC)>);
+
+impl<KC, VC> MapKeysValues<KC, VC> {
+    /// Serializes a map with bytestring keys and values.
+    pub fn serialize<'a, K, V, M, S>(obj: &'a M, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        KC: Container<K>,
+        VC: Container<V>,
+        K: 'a,
+        V: 'a,
+        &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+        S: Serializer,
+    {
+        serialize_map::<KC, _, _, _, _, _>(obj, serializer, |value| {
+            SerializeWith::<VC, V>(value, PhantomData)
+        })
+    }
+
+    /// Deserializes a map with bytestring keys and values.
+    pub fn deserialize<'de, K, V, M, D>(deserializer: D) -> Result<M, D::Error>
+    where
+        KC: Container<K>,
+        VC: Container<V>,
+        M: Default + Extend<(K, V)>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(MapVisitor::<KC, K, DeserializeWith<VC, V>, M>(PhantomData))
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+    use serde::{Deserialize, Serialize};
+
+    use super::{MapKeys, MapKeysValues, SeqOf};
+    use crate::{
+        tests::common::{bin_deserialize, bin_serialize, hr_deserialize, hr_serialize},
+        ArrayLike, Hex, SliceLike,
+    };
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArraySeqStruct(#[serde(with = "SeqOf::<ArrayLike<Hex>>")] Vec<[u8; 2]>);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct VectorSeqStruct(#[serde(with = "SeqOf::<SliceLike<Hex>>")] Vec<Vec<u8>>);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct MapKeysStruct(#[serde(with = "MapKeys::<ArrayLike<Hex>>")] BTreeMap<[u8; 2], u32>);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct BorrowedValuesStruct<'a>(
+        #[serde(borrow, with = "MapKeys::<ArrayLike<Hex>>")] BTreeMap<[u8; 2], &'a str>,
+    );
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct MapKeysValuesStruct(
+        #[serde(with = "MapKeysValues::<ArrayLike<Hex>, SliceLike<Hex>>")]
+        BTreeMap<[u8; 2], Vec<u8>>,
+    );
+
+    #[test]
+    fn roundtrip_arrays() {
+        let val = ArraySeqStruct(vec![[1, 2], [3, 4]]);
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "[\"0x0102\",\"0x0304\"]");
+        assert_eq!(hr_deserialize::<ArraySeqStruct>(&val_str).unwrap(), val);
+
+        let val_bytes = bin_serialize(&val).unwrap();
+        assert_eq!(val_bytes, [0x92, 0xc4, 2, 1, 2, 0xc4, 2, 3, 4]);
+        assert_eq!(bin_deserialize::<ArraySeqStruct>(&val_bytes).unwrap(), val);
+
+        let val = ArraySeqStruct(Vec::new());
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "[]");
+        assert_eq!(hr_deserialize::<ArraySeqStruct>(&val_str).unwrap(), val);
+    }
+
+    #[test]
+    fn roundtrip_vectors() {
+        let val = VectorSeqStruct(vec![vec![1, 2, 3], vec![], vec![4]]);
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "[\"0x010203\",\"0x\",\"0x04\"]");
+        assert_eq!(hr_deserialize::<VectorSeqStruct>(&val_str).unwrap(), val);
+
+        let val_bytes = bin_serialize(&val).unwrap();
+        assert_eq!(bin_deserialize::<VectorSeqStruct>(&val_bytes).unwrap(), val);
+    }
+
+    #[test]
+    fn errors() {
+        // Wrong length of an element
+        assert_eq!(
+            hr_deserialize::<ArraySeqStruct>("[\"0x0102\",\"0x030405\"]").unwrap_err(),
+            "Expected a bytestring of length 2, got 3 at line 1 column 20"
+        );
+
+        // Not a sequence
+        assert_eq!(
+            hr_deserialize::<ArraySeqStruct>("\"0x0102\"").unwrap_err(),
+            "invalid type: string \"0x0102\", expected a sequence of bytestrings at line 1 column 8"
+        );
+
+        // Wrong element type
+        assert_eq!(
+            hr_deserialize::<VectorSeqStruct>("[1]").unwrap_err(),
+            "invalid type: integer `1`, expected a bytestring at line 1 column 2"
+        );
+    }
+
+    #[test]
+    fn roundtrip_map_keys() {
+        let val = MapKeysStruct([([1, 2], 3), ([4, 5], 6)].into());
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "{\"0x0102\":3,\"0x0405\":6}");
+        assert_eq!(hr_deserialize::<MapKeysStruct>(&val_str).unwrap(), val);
+
+        let val_bytes = bin_serialize(&val).unwrap();
+        assert_eq!(val_bytes, [0x82, 0xc4, 2, 1, 2, 3, 0xc4, 2, 4, 5, 6]);
+        assert_eq!(bin_deserialize::<MapKeysStruct>(&val_bytes).unwrap(), val);
+
+        // Values can borrow from the input
+        let val = BorrowedValuesStruct([([1, 2], "a"), ([4, 5], "b")].into());
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(
+            hr_deserialize::<BorrowedValuesStruct<'_>>(&val_str).unwrap(),
+            val
+        );
+    }
+
+    #[test]
+    fn roundtrip_map_keys_values() {
+        let val = MapKeysValuesStruct([([1, 2], vec![3]), ([4, 5], vec![6, 7])].into());
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "{\"0x0102\":\"0x03\",\"0x0405\":\"0x0607\"}");
+        assert_eq!(
+            hr_deserialize::<MapKeysValuesStruct>(&val_str).unwrap(),
+            val
+        );
+
+        let val_bytes = bin_serialize(&val).unwrap();
+        assert_eq!(
+            bin_deserialize::<MapKeysValuesStruct>(&val_bytes).unwrap(),
+            val
+        );
+    }
+
+    #[test]
+    fn map_errors() {
+        // Wrong length of a key
+        assert_eq!(
+            hr_deserialize::<MapKeysStruct>("{\"0x010203\":3}").unwrap_err(),
+            "Expected a bytestring of length 2, got 3 at line 1 column 11"
+        );
+
+        // Wrong value
+        assert_eq!(
+            hr_deserialize::<MapKeysValuesStruct>("{\"0x0102\":3}").unwrap_err(),
+            "invalid type: integer `3`, expected a bytestring at line 1 column 11"
+        );
+
+        // Not a map
+        assert_eq!(
+            hr_deserialize::<MapKeysStruct>("[]").unwrap_err(),
+            "invalid type: sequence, expected a map with bytestring keys at line 1 column 0"
+        );
+    }
+}
@@ -1,3 +1,7 @@
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::convert::Infallible;
 use core::fmt;
 
 use core::marker::PhantomData;
@@ -18,6 +22,7 @@ use crate::low_level;
 /// this is caused by `serde` not being able to communicate to format implementations
 /// that the array has a constant size.
 /// See <https://github.com/serde-rs/serde/issues/2120> for details.
+/// Use [`ArrayTupleLike`] if the length must be omitted.
 ///
 /// Requirements:
 /// - serializer requires the field to implement `AsRef<[u8]>`;
@@ -45,6 +50,45 @@ impl<Enc: Encoding> ArrayLike<Enc> {
     }
 }
 
+/// A container for array-like data, e.g. Rust stack arrays,
+/// serialized as a tuple of bytes in binary formats.
+///
+/// For use in the `#[serde(with)]` field attribute.
+///
+/// Unlike [`ArrayLike`], the length of the array is not serialized,
+/// so formats that are not self-describing (e.g. `bincode` or `postcard`)
+/// will produce exactly `N` bytes.
+/// On the other hand, self-describing formats (e.g. MessagePack or CBOR)
+/// will serialize it as a sequence of integers, which may be less efficient
+/// than the bytestring [`ArrayLike`] produces.
+/// In human-readable formats the array is serialized as an encoded string, same as [`ArrayLike`].
+///
+/// Requirements:
+/// - serializer requires the field to implement `AsRef<[u8]>`;
+/// - deserializer requires the field to implement `TryFrom<[u8; N]>`.
+pub struct ArrayTupleLike<Enc: Encoding>(PhantomData<Enc>);
+
+impl<Enc: Encoding> ArrayTupleLike<Enc> {
+    /// Serializes array-like data.
+    pub fn serialize<T, S>(obj: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        low_level::serialize_array_as_tuple::<Enc, _>(obj.as_ref(), serializer)
+    }
+
+    /// Deserializes into array-like data.
+    pub fn deserialize<'de, T, E, D, const N: usize>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: TryFrom<[u8; N], Error = E>,
+        E: fmt::Display,
+    {
+        low_level::deserialize_array_from_tuple::<Enc, N, _, _, _>(deserializer)
+    }
+}
+
 /// A container for slice-like data, e.g. `Vec<u8>` or `Box<u8>`.
 ///
 /// For use in the `#[serde(with)]` field attribute.
@@ -52,6 +96,9 @@ impl<Enc: Encoding> ArrayLike<Enc> {
 /// Requirements:
 /// - serializer requires the field to implement `AsRef<[u8]>`;
 /// - deserializer requires the field to implement `TryFrom<&[u8]>`.
+///
+/// Deserializing from human-readable formats decodes the string into a temporary buffer,
+/// and therefore requires the `alloc` feature; without it only binary formats are supported.
 pub struct SliceLike<Enc: Encoding>(PhantomData<Enc>);
 
 impl<Enc: Encoding> SliceLike<Enc> {
@@ -85,6 +132,9 @@ impl<Enc: Encoding> SliceLike<Enc> {
 /// Requirements:
 /// - serializer requires the field to implement `AsRef<[u8]>`;
 /// - deserializer requires the field to implement `Clone` and `&Self: TryFrom<&[u8]>`.
+///
+/// Deserializing from human-readable formats decodes the string into a temporary buffer,
+/// and therefore requires the `alloc` feature; without it only binary formats are supported.
 pub struct BorrowedSliceLike<Enc: Encoding>(PhantomData<Enc>);
 
 impl<Enc: Encoding> BorrowedSliceLike<Enc> {
@@ -109,6 +159,86 @@ impl<Enc: Encoding> BorrowedSliceLike<Enc> {
     }
 }
 
+/// A type that can be constructed from bytes borrowed from the deserializer's input,
+/// or from an owned byte vector if borrowing is not possible.
+///
+/// Implemented for `&'de [u8]` (which cannot be constructed from owned bytes)
+/// and `Cow<'de, [u8]>`.
+#[cfg(feature = "alloc")]
+pub trait FromBorrowedBytes<'de>: Sized {
+    /// The error returned if the object cannot be constructed.
+    type Error: fmt::Display;
+
+    /// Creates the object from bytes borrowed from the deserializer's input.
+    fn from_borrowed_bytes(bytes: &'de [u8]) -> Result<Self, Self::Error>;
+
+    /// Creates the object from owned bytes, e.g. the ones decoded from a string.
+    fn from_owned_bytes(bytes: Vec<u8>) -> Result<Self, Self::Error>;
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> FromBorrowedBytes<'de> for &'de [u8] {
+    type Error = &'static str;
+
+    fn from_borrowed_bytes(bytes: &'de [u8]) -> Result<Self, Self::Error> {
+        Ok(bytes)
+    }
+
+    fn from_owned_bytes(_bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        Err("the bytes cannot be borrowed from the input; use `Cow<[u8]>` instead")
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> FromBorrowedBytes<'de> for Cow<'de, [u8]> {
+    type Error = Infallible;
+
+    fn from_borrowed_bytes(bytes: &'de [u8]) -> Result<Self, Self::Error> {
+        Ok(Cow::Borrowed(bytes))
+    }
+
+    fn from_owned_bytes(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        Ok(Cow::Owned(bytes))
+    }
+}
+
+/// A container for bytes borrowed from the deserializer's input,
+/// e.g. `&'de [u8]` or `Cow<'de, [u8]>`.
+///
+/// For use in the `#[serde(with)]` field attribute
+/// (along with `#[serde(borrow)]` for `Cow<'de, [u8]>`).
+///
+/// In binary formats that can lend out their input (e.g. `bincode` or `rmp-serde` deserializing from a slice)
+/// no copy is made. Otherwise, e.g. in human-readable formats where the bytes have to be decoded,
+/// an owned vector is created, so `&'de [u8]` can only be deserialized from such binary formats.
+///
+/// Requirements:
+/// - serializer requires the field to implement `AsRef<[u8]>`;
+/// - deserializer requires the field to implement [`FromBorrowedBytes`].
+#[cfg(feature = "alloc")]
+pub struct BorrowedBytes<Enc: Encoding>(PhantomData<Enc>);
+
+#[cfg(feature = "alloc")]
+impl<Enc: Encoding> BorrowedBytes<Enc> {
+    /// Serializes borrowed bytes.
+    pub fn serialize<T, S>(obj: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        low_level::serialize_slice::<Enc, _>(obj.as_ref(), serializer)
+    }
+
+    /// Deserializes into borrowed bytes, if possible.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: FromBorrowedBytes<'de>,
+    {
+        low_level::deserialize_borrowed_bytes::<Enc, _, _>(deserializer)
+    }
+}
+
 /// A container for boxed array-like data, e.g. `Box<[u8; 4]>`
 /// or `Box<generic_array::GenericArray<...>>`.
 ///
@@ -178,7 +308,346 @@ impl<Enc: Encoding> GenericArray014<Enc> {
     }
 }
 
+/// An optional version of [`ArrayLike`], e.g. for `Option<[u8; N]>`.
+///
+/// For use in the `#[serde(with)]` field attribute
+/// (along with `#[serde(default)]` if the field may be missing).
+///
+/// `None` is serialized with `Serializer::serialize_none()`,
+/// and the contained value the same way as [`ArrayLike`] does.
+pub struct OptionArrayLike<Enc: Encoding>(PhantomData<Enc>);
+
+impl<Enc: Encoding> OptionArrayLike<Enc> {
+    /// Serializes optional array-like data.
+    pub fn serialize<T, S>(obj: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        low_level::serialize_option_slice::<Enc, _>(obj.as_ref().map(AsRef::as_ref), serializer)
+    }
+
+    /// Deserializes into optional array-like data.
+    pub fn deserialize<'de, T, E, D, const N: usize>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: TryFrom<[u8; N], Error = E>,
+        E: fmt::Display,
+    {
+        low_level::deserialize_option_array::<Enc, N, _, _, _>(deserializer)
+    }
+}
+
+/// An optional version of [`ArrayTupleLike`], e.g. for `Option<[u8; N]>`.
+///
+/// For use in the `#[serde(with)]` field attribute
+/// (along with `#[serde(default)]` if the field may be missing).
+///
+/// `None` is serialized with `Serializer::serialize_none()`,
+/// and the contained value the same way as [`ArrayTupleLike`] does.
+pub struct OptionArrayTupleLike<Enc: Encoding>(PhantomData<Enc>);
+
+impl<Enc: Encoding> OptionArrayTupleLike<Enc> {
+    /// Serializes optional array-like data.
+    pub fn serialize<T, S>(obj: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        low_level::serialize_option_array_as_tuple::<Enc, _>(
+            obj.as_ref().map(AsRef::as_ref),
+            serializer,
+        )
+    }
+
+    /// Deserializes into optional array-like data.
+    pub fn deserialize<'de, T, E, D, const N: usize>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: TryFrom<[u8; N], Error = E>,
+        E: fmt::Display,
+    {
+        low_level::deserialize_option_array_from_tuple::<Enc, N, _, _, _>(deserializer)
+    }
+}
+
+/// An optional version of [`SliceLike`], e.g. for `Option<Vec<u8>>`.
+///
+/// For use in the `#[serde(with)]` field attribute
+/// (along with `#[serde(default)]` if the field may be missing).
+///
+/// `None` is serialized with `Serializer::serialize_none()`,
+/// and the contained value the same way as [`SliceLike`] does.
+pub struct OptionSliceLike<Enc: Encoding>(PhantomData<Enc>);
+
+impl<Enc: Encoding> OptionSliceLike<Enc> {
+    /// Serializes optional slice-like data.
+    pub fn serialize<T, S>(obj: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        low_level::serialize_option_slice::<Enc, _>(obj.as_ref().map(AsRef::as_ref), serializer)
+    }
+
+    /// Deserializes into optional slice-like data.
+    pub fn deserialize<'de, T, E, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: for<'a> TryFrom<&'a [u8], Error = E>,
+        E: fmt::Display,
+    {
+        low_level::deserialize_option_slice::<Enc, _, _, _>(deserializer)
+    }
+}
+
+/// An optional version of [`BorrowedSliceLike`].
+///
+/// For use in the `#[serde(with)]` field attribute
+/// (along with `#[serde(default)]` if the field may be missing).
+///
+/// `None` is serialized with `Serializer::serialize_none()`,
+/// and the contained value the same way as [`BorrowedSliceLike`] does.
+pub struct OptionBorrowedSliceLike<Enc: Encoding>(PhantomData<Enc>);
+
+impl<Enc: Encoding> OptionBorrowedSliceLike<Enc> {
+    /// Serializes optional slice-like data.
+    pub fn serialize<T, S>(obj: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        low_level::serialize_option_slice::<Enc, _>(obj.as_ref().map(AsRef::as_ref), serializer)
+    }
+
+    /// Deserializes into optional slice-like data.
+    pub fn deserialize<'de, T, E, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Clone,
+        for<'a> &'a T: TryFrom<&'a [u8], Error = E>,
+        E: fmt::Display,
+    {
+        low_level::deserialize_option_borrowed_slice::<Enc, _, _, _>(deserializer)
+    }
+}
+
+/// An optional version of [`BorrowedBytes`], e.g. for `Option<Cow<'de, [u8]>>`.
+///
+/// For use in the `#[serde(with)]` field attribute
+/// (along with `#[serde(default)]` if the field may be missing).
+///
+/// `None` is serialized with `Serializer::serialize_none()`,
+/// and the contained value the same way as [`BorrowedBytes`] does.
+#[cfg(feature = "alloc")]
+pub struct OptionBorrowedBytes<Enc: Encoding>(PhantomData<Enc>);
+
+#[cfg(feature = "alloc")]
+impl<Enc: Encoding> OptionBorrowedBytes<Enc> {
+    /// Serializes optional borrowed bytes.
+    pub fn serialize<T, S>(obj: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]>,
+        S: Serializer,
+    {
+        low_level::serialize_option_slice::<Enc, _>(obj.as_ref().map(AsRef::as_ref), serializer)
+    }
+
+    /// Deserializes into optional borrowed bytes, if possible.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: FromBorrowedBytes<'de>,
+    {
+        low_level::deserialize_option_borrowed_bytes::<Enc, _, _>(deserializer)
+    }
+}
+
+/// An optional version of [`BoxedArrayLike`], e.g. for `Option<Box<[u8; N]>>`.
+///
+/// For use in the `#[serde(with)]` field attribute
+/// (along with `#[serde(default)]` if the field may be missing).
+///
+/// `None` is serialized with `Serializer::serialize_none()`,
+/// and the contained value the same way as [`BoxedArrayLike`] does.
+pub struct OptionBoxedArrayLike<Enc: Encoding>(PhantomData<Enc>);
+
+impl<Enc: Encoding> OptionBoxedArrayLike<Enc> {
+    /// Serializes optional boxed-array-like data.
+    pub fn serialize<T, S, const N: usize>(
+        obj: &Option<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8; N]>,
+        S: Serializer,
+    {
+        low_level::serialize_option_slice::<Enc, _>(
+            obj.as_ref().map(|obj| obj.as_ref().as_ref()),
+            serializer,
+        )
+    }
+
+    /// Deserializes into optional boxed-array-like data.
+    pub fn deserialize<'de, T, E, D, const N: usize>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: TryFrom<[u8; N], Error = E>,
+        E: fmt::Display,
+    {
+        low_level::deserialize_option_array::<Enc, N, _, _, _>(deserializer)
+    }
+}
+
+/// An optional version of [`GenericArray014`].
+///
+/// For use in the `#[serde(with)]` field attribute
+/// (along with `#[serde(default)]` if the field may be missing).
+///
+/// `None` is serialized with `Serializer::serialize_none()`,
+/// and the contained value the same way as [`GenericArray014`] does.
+#[cfg(feature = "generic-array-014")]
+pub struct OptionGenericArray014<Enc: Encoding>(PhantomData<Enc>);
+
+#[cfg(feature = "generic-array-014")]
+impl<Enc: Encoding> OptionGenericArray014<Enc> {
+    /// Serializes optional array-like data.
+    pub fn serialize<L, S>(
+        obj: &Option<generic_array_014::GenericArray<u8, L>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        L: generic_array_014::ArrayLength<u8>,
+        S: Serializer,
+    {
+        low_level::serialize_option_slice::<Enc, _>(obj.as_ref().map(AsRef::as_ref), serializer)
+    }
+
+    /// Deserializes into optional array-like data.
+    pub fn deserialize<'de, L, D>(
+        deserializer: D,
+    ) -> Result<Option<generic_array_014::GenericArray<u8, L>>, D::Error>
+    where
+        D: Deserializer<'de>,
+        L: generic_array_014::ArrayLength<u8>,
+    {
+        low_level::deserialize_option_generic_array_014::<Enc, L, _>(deserializer)
+    }
+}
+
+/// A container that can be used with an object of type `T`
+/// in adapters like [`SeqOf`](`crate::SeqOf`).
+///
+/// Implemented by the containers from this crate for the corresponding standard types.
+pub trait Container<T> {
+    /// Serializes the object.
+    fn serialize<S: Serializer>(obj: &T, serializer: S) -> Result<S::Ok, S::Error>;
+
+    /// Deserializes the object.
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error>;
+}
+
+impl<Enc: Encoding, const N: usize> Container<[u8; N]> for ArrayLike<Enc> {
+    fn serialize<S: Serializer>(obj: &[u8; N], serializer: S) -> Result<S::Ok, S::Error> {
+        Self::serialize(obj, serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; N], D::Error> {
+        Self::deserialize(deserializer)
+    }
+}
+
+impl<Enc: Encoding, const N: usize> Container<[u8; N]> for ArrayTupleLike<Enc> {
+    fn serialize<S: Serializer>(obj: &[u8; N], serializer: S) -> Result<S::Ok, S::Error> {
+        Self::serialize(obj, serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; N], D::Error> {
+        Self::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Enc: Encoding> Container<Vec<u8>> for SliceLike<Enc> {
+    fn serialize<S: Serializer>(obj: &Vec<u8>, serializer: S) -> Result<S::Ok, S::Error> {
+        Self::serialize(obj, serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        Self::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Enc: Encoding> Container<Box<[u8]>> for SliceLike<Enc> {
+    fn serialize<S: Serializer>(obj: &Box<[u8]>, serializer: S) -> Result<S::Ok, S::Error> {
+        Self::serialize(obj, serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Box<[u8]>, D::Error> {
+        Self::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Enc: Encoding, const N: usize> Container<Box<[u8; N]>> for BoxedArrayLike<Enc> {
+    fn serialize<S: Serializer>(obj: &Box<[u8; N]>, serializer: S) -> Result<S::Ok, S::Error> {
+        Self::serialize(obj, serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Box<[u8; N]>, D::Error> {
+        Self::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "generic-array-014")]
+impl<Enc, L> Container<generic_array_014::GenericArray<u8, L>> for GenericArray014<Enc>
+where
+    Enc: Encoding,
+    L: generic_array_014::ArrayLength<u8>,
+{
+    fn serialize<S: Serializer>(
+        obj: &generic_array_014::GenericArray<u8, L>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Self::serialize(obj, serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<generic_array_014::GenericArray<u8, L>, D::Error> {
+        Self::deserialize(deserializer)
+    }
+}
+
+impl<Enc: Encoding, const N: usize> Container<Option<[u8; N]>> for OptionArrayLike<Enc> {
+    fn serialize<S: Serializer>(obj: &Option<[u8; N]>, serializer: S) -> Result<S::Ok, S::Error> {
+        Self::serialize(obj, serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<[u8; N]>, D::Error> {
+        Self::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Enc: Encoding> Container<Option<Vec<u8>>> for OptionSliceLike<Enc> {
+    fn serialize<S: Serializer>(obj: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error> {
+        Self::serialize(obj, serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<u8>>, D::Error> {
+        Self::deserialize(deserializer)
+    }
+}
+
+// A plain `cfg(test)` is needed for `allow-unwrap-in-tests` to apply to the helper functions.
 #[cfg(test)]
+#[cfg(feature = "alloc")]
 mod tests {
     use alloc::{boxed::Box, vec::Vec};
 
@@ -218,4 +687,122 @@ mod tests {
         let val_back = rmp_serde::from_slice::<BoxedArrayStruct>(&val_bytes).unwrap();
         assert_eq!(val, val_back);
     }
+
+    mod option {
+        use alloc::{borrow::Cow, boxed::Box, string::ToString, vec::Vec};
+        use core::fmt::Debug;
+
+        use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+        use crate::{
+            encoding::Hex, OptionArrayLike, OptionArrayTupleLike, OptionBorrowedBytes,
+            OptionBoxedArrayLike, OptionSliceLike,
+        };
+
+        #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+        struct OptionStruct {
+            #[serde(default, with = "OptionArrayLike::<Hex>")]
+            array: Option<[u8; 4]>,
+            #[serde(default, with = "OptionArrayTupleLike::<Hex>")]
+            array_tuple: Option<[u8; 4]>,
+            #[serde(default, with = "OptionSliceLike::<Hex>")]
+            slice: Option<Vec<u8>>,
+            #[serde(default, with = "OptionBoxedArrayLike::<Hex>")]
+            boxed_array: Option<Box<[u8; 4]>>,
+        }
+
+        fn check_roundtrip<T: Debug + PartialEq + Serialize + DeserializeOwned>(val: &T) {
+            let val_bytes = rmp_serde::to_vec(val).unwrap();
+            let val_back = rmp_serde::from_slice::<T>(&val_bytes).unwrap();
+            assert_eq!(val, &val_back);
+
+            let val_str = serde_json::to_string(val).unwrap();
+            let val_back = serde_json::from_str::<T>(&val_str).unwrap();
+            assert_eq!(val, &val_back);
+        }
+
+        #[test]
+        fn roundtrip_option() {
+            let val = OptionStruct {
+                array: Some([1, 2, 3, 4]),
+                array_tuple: Some([5, 6, 7, 8]),
+                slice: Some([1, 2, 3].into()),
+                boxed_array: Some([1, 2, 3, 4].into()),
+            };
+            check_roundtrip(&val);
+            assert_eq!(
+                serde_json::to_string(&val).unwrap(),
+                concat![
+                    "{\"array\":\"0x01020304\",\"array_tuple\":\"0x05060708\",",
+                    "\"slice\":\"0x010203\",\"boxed_array\":\"0x01020304\"}"
+                ]
+            );
+
+            let val = OptionStruct {
+                array: None,
+                array_tuple: None,
+                slice: None,
+                boxed_array: None,
+            };
+            check_roundtrip(&val);
+            assert_eq!(
+                serde_json::to_string(&val).unwrap(),
+                "{\"array\":null,\"array_tuple\":null,\"slice\":null,\"boxed_array\":null}"
+            );
+
+            // Missing fields are deserialized as `None` thanks to `#[serde(default)]`
+            assert_eq!(serde_json::from_str::<OptionStruct>("{}").unwrap(), val);
+        }
+
+        #[test]
+        fn option_errors() {
+            assert_eq!(
+                serde_json::from_str::<OptionStruct>("{\"array\":\"0x010203\"}")
+                    .unwrap_err()
+                    .to_string(),
+                "Expected a bytestring of length 4, got 3 at line 1 column 19"
+            );
+            assert_eq!(
+                serde_json::from_str::<OptionStruct>("{\"slice\":1}")
+                    .unwrap_err()
+                    .to_string(),
+                "invalid type: integer `1`, expected a bytestring at line 1 column 10"
+            );
+        }
+
+        #[test]
+        fn roundtrip_option_borrowed_bytes() {
+            #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+            struct OptionCowStruct<'a>(
+                #[serde(borrow, with = "OptionBorrowedBytes::<Hex>")] Option<Cow<'a, [u8]>>,
+            );
+
+            let val = OptionCowStruct(Some(Cow::Borrowed(&[1, 2, 3, 4])));
+            let val_bytes = rmp_serde::to_vec(&val).unwrap();
+            let val_back = rmp_serde::from_slice::<OptionCowStruct<'_>>(&val_bytes).unwrap();
+            assert!(matches!(val_back.0, Some(Cow::Borrowed(_))));
+            assert_eq!(val, val_back);
+
+            let val = OptionCowStruct(None);
+            let val_bytes = rmp_serde::to_vec(&val).unwrap();
+            let val_back = rmp_serde::from_slice::<OptionCowStruct<'_>>(&val_bytes).unwrap();
+            assert_eq!(val, val_back);
+        }
+
+        #[cfg(feature = "generic-array-014")]
+        #[test]
+        fn roundtrip_option_generic_array_014() {
+            use generic_array_014::{typenum::U4, GenericArray};
+
+            use crate::OptionGenericArray014;
+
+            #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+            struct OptionGenericArrayStruct(
+                #[serde(with = "OptionGenericArray014::<Hex>")] Option<GenericArray<u8, U4>>,
+            );
+
+            check_roundtrip(&OptionGenericArrayStruct(Some([1, 2, 3, 4].into())));
+            check_roundtrip(&OptionGenericArrayStruct(None));
+        }
+    }
 }
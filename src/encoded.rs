@@ -0,0 +1,240 @@
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::encoding::Encoding;
+use crate::low_level;
+
+/// A transparent wrapper serializing the wrapped bytestring with the encoding `Enc`.
+///
+/// Unlike the containers, which can only be used in the `#[serde(with)]` field attribute,
+/// this type implements `Serialize` and `Deserialize` itself,
+/// so it can be nested anywhere, e.g. `Vec<Encoded<[u8; 32], Hex>>` or as a map key.
+///
+/// Serialization is supported for any `T: AsRef<[u8]>`.
+/// Deserialization is supported for arrays (`[u8; N]`, `Box<[u8; N]>`, and `GenericArray`
+/// from `generic-array=0.14`), deserialized the same way as [`ArrayLike`](`crate::ArrayLike`) does,
+/// and slices (`Vec<u8>` and `Box<[u8]>`), deserialized the same way as [`SliceLike`](`crate::SliceLike`) does.
+///
+/// The comparison, hashing and formatting traits are forwarded to `T`.
+#[repr(transparent)]
+pub struct Encoded<T, Enc>(T, PhantomData<Enc>);
+
+impl<T, Enc> Encoded<T, Enc> {
+    /// Wraps the given object.
+    pub const fn new(value: T) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// Returns the wrapped object.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, Enc> From<T> for Encoded<T, Enc> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T, Enc> Deref for Encoded<T, Enc> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, Enc> DerefMut for Encoded<T, Enc> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: AsRef<[u8]>, Enc> AsRef<[u8]> for Encoded<T, Enc> {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl<T: Clone, Enc> Clone for Encoded<T, Enc> {
+    fn clone(&self) -> Self {
+        Self::new(self.0.clone())
+    }
+}
+
+impl<T: Copy, Enc> Copy for Encoded<T, Enc> {}
+
+impl<T: Default, Enc> Default for Encoded<T, Enc> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug, Enc> fmt::Debug for Encoded<T, Enc> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: PartialEq, Enc> PartialEq for Encoded<T, Enc> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: Eq, Enc> Eq for Encoded<T, Enc> {}
+
+impl<T: PartialOrd, Enc> PartialOrd for Encoded<T, Enc> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<T: Ord, Enc> Ord for Encoded<T, Enc> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T: Hash, Enc> Hash for Encoded<T, Enc> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<T: AsRef<[u8]>, Enc: Encoding> Serialize for Encoded<T, Enc> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        low_level::serialize_slice::<Enc, _>(self.0.as_ref(), serializer)
+    }
+}
+
+impl<'de, Enc: Encoding, const N: usize> Deserialize<'de> for Encoded<[u8; N], Enc> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        low_level::deserialize_array::<Enc, N, _, _, _>(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, Enc: Encoding, const N: usize> Deserialize<'de> for Encoded<Box<[u8; N]>, Enc> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        low_level::deserialize_array::<Enc, N, _, _, _>(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(feature = "generic-array-014")]
+impl<'de, Enc, L> Deserialize<'de> for Encoded<generic_array_014::GenericArray<u8, L>, Enc>
+where
+    Enc: Encoding,
+    L: generic_array_014::ArrayLength<u8>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        low_level::deserialize_generic_array_014::<Enc, L, _>(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, Enc: Encoding> Deserialize<'de> for Encoded<Vec<u8>, Enc> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        low_level::deserialize_slice::<Enc, _, _, _>(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, Enc: Encoding> Deserialize<'de> for Encoded<Box<[u8]>, Enc> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        low_level::deserialize_slice::<Enc, _, _, _>(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+    use serde::{Deserialize, Serialize};
+
+    use super::Encoded;
+    use crate::{
+        tests::common::{bin_deserialize, bin_serialize, hr_deserialize, hr_serialize},
+        Hex,
+    };
+
+    type Hash = Encoded<[u8; 2], Hex>;
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Nested {
+        hashes: Vec<Hash>,
+        maybe_hash: Option<Hash>,
+        result: Result<Hash, u32>,
+        by_hash: BTreeMap<Hash, u32>,
+    }
+
+    #[test]
+    fn roundtrip_nested() {
+        let val = Nested {
+            hashes: vec![[1, 2].into(), [3, 4].into()],
+            maybe_hash: Some([5, 6].into()),
+            result: Ok([7, 8].into()),
+            by_hash: [([9, 10].into(), 1)].into(),
+        };
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(
+            val_str,
+            concat![
+                "{\"hashes\":[\"0x0102\",\"0x0304\"],\"maybe_hash\":\"0x0506\",",
+                "\"result\":{\"Ok\":\"0x0708\"},\"by_hash\":{\"0x090a\":1}}"
+            ]
+        );
+        assert_eq!(hr_deserialize::<Nested>(&val_str).unwrap(), val);
+
+        let val_bytes = bin_serialize(&val).unwrap();
+        assert_eq!(bin_deserialize::<Nested>(&val_bytes).unwrap(), val);
+    }
+
+    #[test]
+    fn roundtrip_slice() {
+        let val: Vec<Encoded<Vec<u8>, Hex>> = vec![vec![1, 2, 3].into(), vec![].into()];
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "[\"0x010203\",\"0x\"]");
+        assert_eq!(
+            hr_deserialize::<Vec<Encoded<Vec<u8>, Hex>>>(&val_str).unwrap(),
+            val
+        );
+
+        let val_bytes = bin_serialize(&val).unwrap();
+        assert_eq!(
+            bin_deserialize::<Vec<Encoded<Vec<u8>, Hex>>>(&val_bytes).unwrap(),
+            val
+        );
+    }
+
+    #[test]
+    fn forwarding() {
+        let mut val = Hash::new([1, 2]);
+        assert_eq!(*val, [1, 2]);
+        *val = [3, 2];
+        assert_eq!(val.as_ref(), [3, 2]);
+        assert!(val > Hash::new([1, 2]));
+        assert_eq!(alloc::format!("{val:?}"), "[3, 2]");
+        assert_eq!(val.into_inner(), [3, 2]);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            hr_deserialize::<Vec<Hash>>("[\"0x010203\"]").unwrap_err(),
+            "Expected a bytestring of length 2, got 3 at line 1 column 11"
+        );
+    }
+}
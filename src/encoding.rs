@@ -1,17 +1,81 @@
 //! Possible encodings for byte sequences when serializing into human-readable formats.
 
+mod dynamic;
+mod error;
+mod lenient;
 mod traits;
 
 #[cfg(any(feature = "hex", test))]
 mod hex;
 
+// Only useful if any of the encodings with a multibase code is enabled.
+#[cfg(any(
+    feature = "hex",
+    feature = "base64",
+    feature = "base32",
+    feature = "base58",
+    test
+))]
+mod multibase;
+
 #[cfg(feature = "base64")]
 mod base64;
 
+#[cfg(feature = "base32")]
+mod base32;
+
+#[cfg(feature = "base58")]
+mod base58;
+
+#[cfg(feature = "bech32")]
+mod bech32;
+
+#[cfg(feature = "base85")]
+mod base85;
+
+#[cfg(feature = "eip55")]
+mod eip55;
+
+#[cfg(feature = "scoped")]
+mod scoped;
+
+pub use dynamic::{DynEncoding, EncodingKind};
+pub use error::{DecodeError, EncodeError};
+pub use lenient::Lenient;
 pub use traits::Encoding;
 
+#[cfg(feature = "scoped")]
+pub use scoped::{with_encoding, Scoped};
+
 #[cfg(any(feature = "hex", test))]
-pub use self::hex::Hex;
+pub use self::hex::{Hex, HexNoPrefix, HexNoPrefixUpper, HexOptionalPrefix, HexUpper};
+
+#[cfg(any(
+    feature = "hex",
+    feature = "base64",
+    feature = "base32",
+    feature = "base58",
+    test
+))]
+pub use self::multibase::{Multibase, MultibaseCode};
 
 #[cfg(feature = "base64")]
-pub use self::base64::{Base64, Base64Url};
+pub use self::base64::{
+    Base64, Base64AnyPadding, Base64Mime, Base64Padded, Base64Url, Base64UrlAnyPadding,
+    Base64UrlPadded, Base64Wrapped,
+};
+
+#[cfg(feature = "base32")]
+pub use self::base32::{Base32, Base32Hex};
+
+#[cfg(feature = "base58")]
+pub use self::base58::{Base58, Base58Check};
+
+#[cfg(feature = "bech32")]
+pub use self::bech32::{Bech32, Bech32Hrp, Bech32m};
+
+#[cfg(feature = "base85")]
+pub use self::base85::{Ascii85, Base85, Z85};
+
+#[cfg(feature = "eip55")]
+pub use self::eip55::Eip55;
@@ -0,0 +1,156 @@
+use core::fmt;
+
+use data_encoding::{DecodeKind, BASE32HEX_NOPAD, BASE32_NOPAD};
+
+use super::{DecodeError, Encoding};
+
+fn decode_error(string: &str, err: data_encoding::DecodeError) -> DecodeError {
+    match err.kind {
+        DecodeKind::Length => DecodeError::InvalidLength {
+            length: string.len(),
+            expected: "a valid number of base32 characters",
+        },
+        DecodeKind::Padding => DecodeError::InvalidPadding,
+        // `Trailing` means that the last character has non-zero unused bits
+        DecodeKind::Symbol | DecodeKind::Trailing => {
+            DecodeError::invalid_character(string, err.position)
+        }
+    }
+}
+
+fn decode_into(
+    encoding: &data_encoding::Encoding,
+    string: &str,
+    buffer: &mut [u8],
+) -> Result<usize, DecodeError> {
+    let len = encoding
+        .decode_len(string.len())
+        .map_err(|err| decode_error(string, err))?;
+    if let Some(dest) = buffer.get_mut(..len) {
+        return encoding
+            .decode_mut(string.as_bytes(), dest)
+            .map_err(|err| decode_error(string, err.error));
+    }
+
+    // Still validate the string (in chunks of whole 8-character groups, to avoid allocating),
+    // so that the error does not depend on the buffer length.
+    let mut scratch = [0u8; 160];
+    let mut chunk_start = 0;
+    for chunk in string.as_bytes().chunks(256) {
+        let chunk_len = encoding
+            .decode_len(chunk.len())
+            .map_err(|err| decode_error(string, err))?;
+        let dest = scratch.get_mut(..chunk_len).unwrap_or_default();
+        encoding.decode_mut(chunk, dest).map_err(|err| {
+            let mut err = err.error;
+            err.position += chunk_start;
+            decode_error(string, err)
+        })?;
+        chunk_start += chunk.len();
+    }
+    Ok(len)
+}
+
+/// Encodes the byte sequence into a base32 representation.
+///
+/// Uses the standard RFC 4648 base32 alphabet (`A-Z` and `2-7`) and does not include padding.
+pub struct Base32;
+
+impl Encoding for Base32 {
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        BASE32_NOPAD.encode_write(bytes, writer)
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_into(&BASE32_NOPAD, string, buffer)
+    }
+}
+
+/// Encodes the byte sequence into a base32-hex representation.
+///
+/// Uses the RFC 4648 "extended hex" base32 alphabet (`0-9` and `A-V`)
+/// and does not include padding.
+pub struct Base32Hex;
+
+impl Encoding for Base32Hex {
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        BASE32HEX_NOPAD.encode_write(bytes, writer)
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_into(&BASE32HEX_NOPAD, string, buffer)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::{Base32, Base32Hex};
+    use crate::{
+        tests::common::{hr_deserialize, hr_serialize},
+        ArrayLike,
+    };
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStruct(#[serde(with = "ArrayLike::<Base32>")] [u8; 4]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStructHex(#[serde(with = "ArrayLike::<Base32Hex>")] [u8; 4]);
+
+    #[test]
+    fn roundtrip() {
+        let val = ArrayStruct([1, 0xf2, 63, 0xf4]);
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "\"AHZD75A\"");
+        let val_back = hr_deserialize::<ArrayStruct>(&val_str).unwrap();
+        assert_eq!(val, val_back);
+    }
+
+    #[test]
+    fn roundtrip_hex() {
+        let val = ArrayStructHex([1, 0xf2, 63, 0xf4]);
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "\"07P3VT0\"");
+        let val_back = hr_deserialize::<ArrayStructHex>(&val_str).unwrap();
+        assert_eq!(val, val_back);
+    }
+
+    #[test]
+    fn errors() {
+        // A character outside of the alphabet
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>("\"AHZD85A\"").unwrap_err(),
+            concat![
+                "invalid value: string \"AHZD85A\", ",
+                "expected a valid character at offset 4 at line 1 column 9"
+            ]
+        );
+        // The standard alphabet is not a valid base32-hex
+        assert_eq!(
+            hr_deserialize::<ArrayStructHex>("\"AHZD75A\"").unwrap_err(),
+            concat![
+                "invalid value: string \"AHZD75A\", ",
+                "expected a valid character at offset 2 at line 1 column 9"
+            ]
+        );
+        // Padding is not accepted
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>("\"AHZD7==\"").unwrap_err(),
+            concat![
+                "invalid value: string \"AHZD7==\", ",
+                "expected a valid character at offset 5 at line 1 column 9"
+            ]
+        );
+        // An invalid character is reported even if the string is too long for the target
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>("\"AHZD75AAA8\"").unwrap_err(),
+            concat![
+                "invalid value: string \"AHZD75AAA8\", ",
+                "expected a valid character at offset 9 at line 1 column 12"
+            ]
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::{DecodeError, Encoding};
+
+fn decode_error(string: &str, err: bs58::decode::Error) -> DecodeError {
+    match err {
+        bs58::decode::Error::InvalidCharacter { index, .. }
+        | bs58::decode::Error::NonAsciiCharacter { index } => {
+            DecodeError::invalid_character(string, index)
+        }
+        bs58::decode::Error::InvalidChecksum { .. } => DecodeError::ChecksumMismatch,
+        // `NoChecksum` is the only other error that can happen when decoding into a vector
+        _ => DecodeError::InvalidLength {
+            length: string.len(),
+            expected: "base58check-encoded bytes including a 4-byte checksum",
+        },
+    }
+}
+
+fn copy_into(bytes: &[u8], buffer: &mut [u8]) -> usize {
+    if let Some(dest) = buffer.get_mut(..bytes.len()) {
+        dest.copy_from_slice(bytes);
+    }
+    bytes.len()
+}
+
+/// Encodes the byte sequence into a base58 representation.
+///
+/// Uses the Bitcoin base58 alphabet.
+///
+/// Note that base58 cannot be encoded or decoded in a streaming fashion,
+/// so this encoding always allocates intermediate buffers.
+pub struct Base58;
+
+impl Encoding for Base58 {
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        writer.write_str(&bs58::encode(bytes).into_string())
+    }
+
+    fn decode(string: &str) -> Result<Vec<u8>, DecodeError> {
+        bs58::decode(string)
+            .into_vec()
+            .map_err(|err| decode_error(string, err))
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        let bytes = Self::decode(string)?;
+        Ok(copy_into(&bytes, buffer))
+    }
+}
+
+/// Encodes the byte sequence into a base58check representation.
+///
+/// Uses the Bitcoin base58 alphabet, and appends a 4-byte checksum
+/// (the first bytes of the double SHA256 hash of the payload) before encoding.
+/// The checksum is verified and stripped on decoding.
+///
+/// Note that base58 cannot be encoded or decoded in a streaming fashion,
+/// so this encoding always allocates intermediate buffers.
+pub struct Base58Check;
+
+impl Encoding for Base58Check {
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        writer.write_str(&bs58::encode(bytes).with_check().into_string())
+    }
+
+    fn decode(string: &str) -> Result<Vec<u8>, DecodeError> {
+        bs58::decode(string)
+            .with_check(None)
+            .into_vec()
+            .map_err(|err| decode_error(string, err))
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        let bytes = Self::decode(string)?;
+        Ok(copy_into(&bytes, buffer))
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::{Base58, Base58Check};
+    use crate::{
+        tests::common::{hr_deserialize, hr_serialize},
+        ArrayLike,
+    };
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStruct(#[serde(with = "ArrayLike::<Base58>")] [u8; 4]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStructCheck(#[serde(with = "ArrayLike::<Base58Check>")] [u8; 4]);
+
+    #[test]
+    fn roundtrip() {
+        let val = ArrayStruct([0, 0xf2, 63, 0xf4]);
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "\"12QNQb\"");
+        let val_back = hr_deserialize::<ArrayStruct>(&val_str).unwrap();
+        assert_eq!(val, val_back);
+    }
+
+    #[test]
+    fn roundtrip_check() {
+        let val = ArrayStructCheck([0, 0xf2, 63, 0xf4]);
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "\"1ABT8n2t86g\"");
+        let val_back = hr_deserialize::<ArrayStructCheck>(&val_str).unwrap();
+        assert_eq!(val, val_back);
+    }
+
+    #[test]
+    fn errors() {
+        // A character outside of the alphabet
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>("\"1Ol\"").unwrap_err(),
+            "invalid value: string \"1Ol\", expected a valid character at offset 1 at line 1 column 5"
+        );
+
+        // Checksum mismatch
+        assert_eq!(
+            hr_deserialize::<ArrayStructCheck>("\"1ABT8n2t86h\"").unwrap_err(),
+            concat![
+                "invalid value: string \"1ABT8n2t86h\", ",
+                "expected a valid checksum at line 1 column 13"
+            ]
+        );
+    }
+}
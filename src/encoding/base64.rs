@@ -1,9 +1,167 @@
-use alloc::{string::String, vec::Vec};
+use core::{
+    cmp::min,
+    fmt::{self, Write as _},
+};
 
-use base64::{engine::general_purpose, Engine as _};
-use serde::de;
+use base64::{
+    alphabet,
+    display::Base64Display,
+    engine::{general_purpose, DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    DecodeSliceError, Engine as _,
+};
 
-use super::Encoding;
+use super::{DecodeError, Encoding};
+
+const INDIFFERENT_PAD: GeneralPurposeConfig = GeneralPurposeConfig::new()
+    .with_encode_padding(false)
+    .with_decode_padding_mode(DecodePaddingMode::Indifferent);
+
+const STANDARD_INDIFFERENT_PAD: GeneralPurpose =
+    GeneralPurpose::new(&alphabet::STANDARD, INDIFFERENT_PAD);
+
+const URL_SAFE_INDIFFERENT_PAD: GeneralPurpose =
+    GeneralPurpose::new(&alphabet::URL_SAFE, INDIFFERENT_PAD);
+
+/// Converts the engine error; `length` is the number of base64 characters in the string,
+/// and `to_offset` maps an offset in the decoded input to the offset in the original string.
+fn decode_error(
+    string: &str,
+    err: DecodeSliceError,
+    length: usize,
+    to_offset: impl FnOnce(usize) -> usize,
+) -> DecodeError {
+    match err {
+        DecodeSliceError::DecodeError(
+            base64::DecodeError::InvalidByte(offset, _)
+            | base64::DecodeError::InvalidLastSymbol(offset, _),
+        ) => DecodeError::invalid_character(string, to_offset(offset)),
+        // `OutputSliceTooSmall` only happens if the estimated length is too small,
+        // that is for invalid strings
+        DecodeSliceError::DecodeError(base64::DecodeError::InvalidLength(_))
+        | DecodeSliceError::OutputSliceTooSmall => DecodeError::InvalidLength {
+            length,
+            expected: "a valid number of base64 characters",
+        },
+        DecodeSliceError::DecodeError(base64::DecodeError::InvalidPadding) => {
+            DecodeError::InvalidPadding
+        }
+    }
+}
+
+/// Decodes the base64 characters of `string` yielded (along with their offsets) by `symbols`.
+///
+/// The input is decoded in chunks (of a length divisible by 4) to avoid allocating a copy of it.
+/// If `buffer` is too small, the chunks are decoded into a scratch buffer,
+/// so that invalid strings are reported as such regardless of the length of `buffer`.
+fn decode_symbols<I: Iterator<Item = (usize, u8)>>(
+    engine: &GeneralPurpose,
+    string: &str,
+    symbols: impl Fn() -> I,
+    buffer: &mut [u8],
+) -> Result<usize, DecodeError> {
+    // Maps the index among the symbols to the offset in `string`.
+    let to_offset = |index: usize| {
+        symbols()
+            .nth(index)
+            .map_or(string.len(), |(offset, _)| offset)
+    };
+    let error = |err, chunk_start: usize| {
+        decode_error(string, err, symbols().count(), |index| {
+            to_offset(chunk_start + index)
+        })
+    };
+
+    // Exact for valid strings (padded or not); invalid ones will be rejected by the engine.
+    let len = symbols().filter(|(_, c)| *c != b'=').count() * 3 / 4;
+    let mut dest = buffer.get_mut(..len);
+
+    let mut symbols = symbols().map(|(_, c)| c).peekable();
+    let mut chunk = [0u8; 256];
+    let mut scratch = [0u8; 192];
+    let mut chunk_start = 0;
+    let mut offset = 0;
+    loop {
+        let mut chunk_len = 0;
+        for (slot, c) in chunk.iter_mut().zip(&mut symbols) {
+            *slot = c;
+            chunk_len += 1;
+        }
+        if chunk_len == 0 {
+            break;
+        }
+
+        let input = chunk.get(..chunk_len).unwrap_or_default();
+        let output = match dest.as_deref_mut() {
+            Some(dest) => dest.get_mut(offset..).unwrap_or_default(),
+            None => &mut scratch,
+        };
+        offset += engine
+            .decode_slice(input, output)
+            .map_err(|err| error(err, chunk_start))?;
+
+        // The engine accepts padding at the end of the chunk, but it is only allowed
+        // at the end of the whole string.
+        if symbols.peek().is_some() {
+            if let Some(index) = input.iter().position(|c| *c == b'=') {
+                return Err(DecodeError::invalid_character(
+                    string,
+                    to_offset(chunk_start + index),
+                ));
+            }
+        }
+        chunk_start += chunk_len;
+    }
+
+    Ok(len)
+}
+
+fn decode_into(
+    engine: &GeneralPurpose,
+    string: &str,
+    buffer: &mut [u8],
+) -> Result<usize, DecodeError> {
+    decode_symbols(engine, string, || string.bytes().enumerate(), buffer)
+}
+
+/// A writer inserting a line feed after every `width` characters
+/// (except at the very end of the output).
+struct LineWrapper<'a, W> {
+    writer: &'a mut W,
+    width: usize,
+    column: usize,
+}
+
+impl<W: fmt::Write> fmt::Write for LineWrapper<'_, W> {
+    fn write_str(&mut self, mut s: &str) -> fmt::Result {
+        // The base64 output is ASCII, so any byte index is a character boundary.
+        while !s.is_empty() {
+            if self.column == self.width {
+                self.writer.write_char('\n')?;
+                self.column = 0;
+            }
+            let (line, rest) = s.split_at(min(self.width - self.column, s.len()));
+            self.writer.write_str(line)?;
+            self.column += line.len();
+            s = rest;
+        }
+        Ok(())
+    }
+}
+
+/// Decodes a base64 string ignoring any ASCII whitespace in it.
+fn decode_wrapped_into(
+    engine: &GeneralPurpose,
+    string: &str,
+    buffer: &mut [u8],
+) -> Result<usize, DecodeError> {
+    let symbols = || {
+        string
+            .bytes()
+            .enumerate()
+            .filter(|(_, c)| !c.is_ascii_whitespace())
+    };
+    decode_symbols(engine, string, symbols, buffer)
+}
 
 /// Encodes the byte sequence into a base64 representation.
 ///
@@ -11,14 +169,16 @@ use super::Encoding;
 pub struct Base64;
 
 impl Encoding for Base64 {
-    fn encode(bytes: &[u8]) -> String {
-        general_purpose::STANDARD_NO_PAD.encode(bytes)
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        write!(
+            writer,
+            "{}",
+            Base64Display::new(bytes, &general_purpose::STANDARD_NO_PAD)
+        )
     }
 
-    fn decode<E: de::Error>(string: &str) -> Result<Vec<u8>, E> {
-        general_purpose::STANDARD_NO_PAD
-            .decode(string)
-            .map_err(de::Error::custom)
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_into(&general_purpose::STANDARD_NO_PAD, string, buffer)
     }
 }
 
@@ -29,25 +189,133 @@ impl Encoding for Base64 {
 pub struct Base64Url;
 
 impl Encoding for Base64Url {
-    fn encode(bytes: &[u8]) -> String {
-        general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        write!(
+            writer,
+            "{}",
+            Base64Display::new(bytes, &general_purpose::URL_SAFE_NO_PAD)
+        )
     }
 
-    fn decode<E: de::Error>(string: &str) -> Result<Vec<u8>, E> {
-        general_purpose::URL_SAFE_NO_PAD
-            .decode(string)
-            .map_err(de::Error::custom)
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_into(&general_purpose::URL_SAFE_NO_PAD, string, buffer)
     }
 }
 
-#[cfg(test)]
+/// Encodes the byte sequence into a padded base64 representation.
+///
+/// Uses the standard base64 alphabet (with `+` and `/`) and includes padding;
+/// padding is required on decoding.
+pub struct Base64Padded;
+
+impl Encoding for Base64Padded {
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        write!(
+            writer,
+            "{}",
+            Base64Display::new(bytes, &general_purpose::STANDARD)
+        )
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_into(&general_purpose::STANDARD, string, buffer)
+    }
+}
+
+/// Encodes the byte sequence into a padded base64-url representation.
+///
+/// Uses the URL-safe base64 alphabet (with `-` and `_` instead of `+` and `/`)
+/// and includes padding; padding is required on decoding.
+pub struct Base64UrlPadded;
+
+impl Encoding for Base64UrlPadded {
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        write!(
+            writer,
+            "{}",
+            Base64Display::new(bytes, &general_purpose::URL_SAFE)
+        )
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_into(&general_purpose::URL_SAFE, string, buffer)
+    }
+}
+
+/// Encodes the byte sequence the same way as [`Base64`] does,
+/// but accepts both padded and unpadded strings on decoding.
+pub struct Base64AnyPadding;
+
+impl Encoding for Base64AnyPadding {
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        Base64::encode_to(bytes, writer)
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_into(&STANDARD_INDIFFERENT_PAD, string, buffer)
+    }
+}
+
+/// Encodes the byte sequence the same way as [`Base64Url`] does,
+/// but accepts both padded and unpadded strings on decoding.
+pub struct Base64UrlAnyPadding;
+
+impl Encoding for Base64UrlAnyPadding {
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        Base64Url::encode_to(bytes, writer)
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_into(&URL_SAFE_INDIFFERENT_PAD, string, buffer)
+    }
+}
+
+/// Encodes the byte sequence into a padded base64 representation
+/// split into lines of `WIDTH` characters separated by line feeds.
+///
+/// Uses the standard base64 alphabet (with `+` and `/`).
+/// On decoding, any ASCII whitespace is ignored, and padding is optional.
+///
+/// If `WIDTH` is 0, no line breaks are inserted.
+pub struct Base64Wrapped<const WIDTH: usize>;
+
+impl<const WIDTH: usize> Encoding for Base64Wrapped<WIDTH> {
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        let display = Base64Display::new(bytes, &general_purpose::STANDARD);
+        if WIDTH == 0 {
+            return write!(writer, "{display}");
+        }
+        let mut wrapper = LineWrapper {
+            writer,
+            width: WIDTH,
+            column: 0,
+        };
+        write!(wrapper, "{display}")
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_wrapped_into(&STANDARD_INDIFFERENT_PAD, string, buffer)
+    }
+}
+
+/// Base64 wrapped into lines of 76 characters, as in MIME (RFC 2045),
+/// but separated by line feeds instead of CRLF.
+pub type Base64Mime = Base64Wrapped<76>;
+
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
-    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
 
     use serde::{Deserialize, Serialize};
 
-    use super::{Base64, Base64Url};
-    use crate::ArrayLike;
+    use super::{
+        Base64, Base64AnyPadding, Base64Mime, Base64Padded, Base64Url, Base64UrlAnyPadding,
+        Base64UrlPadded, Base64Wrapped,
+    };
+    use crate::{
+        tests::common::{hr_deserialize, hr_serialize},
+        ArrayLike, DecodeError, Encoding,
+    };
 
     #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
     struct ArrayStruct(#[serde(with = "ArrayLike::<Base64>")] [u8; 4]);
@@ -55,12 +323,27 @@ mod tests {
     #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
     struct ArrayStructUrlSafe(#[serde(with = "ArrayLike::<Base64Url>")] [u8; 4]);
 
-    fn hr_serialize<T: Serialize>(value: T) -> Result<String, String> {
-        serde_json::to_string(&value).map_err(|err| err.to_string())
-    }
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStructPadded(#[serde(with = "ArrayLike::<Base64Padded>")] [u8; 4]);
 
-    fn hr_deserialize<'de, T: Deserialize<'de>>(string: &'de str) -> Result<T, String> {
-        serde_json::from_str::<T>(string).map_err(|err| err.to_string())
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStructUrlSafePadded(#[serde(with = "ArrayLike::<Base64UrlPadded>")] [u8; 4]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStructAnyPadding(#[serde(with = "ArrayLike::<Base64AnyPadding>")] [u8; 4]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStructUrlSafeAnyPadding(
+        #[serde(with = "ArrayLike::<Base64UrlAnyPadding>")] [u8; 4],
+    );
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStructWrapped(#[serde(with = "ArrayLike::<Base64Wrapped<4>>")] [u8; 10]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct LongArrayStructMime {
+        #[serde(with = "ArrayLike::<Base64Mime>")]
+        value: [u8; 256],
     }
 
     #[test]
@@ -82,4 +365,191 @@ mod tests {
         let val_back = hr_deserialize::<ArrayStructUrlSafe>(&val_str).unwrap();
         assert_eq!(val, val_back);
     }
+
+    #[test]
+    fn roundtrip_padded() {
+        let val = ArrayStructPadded([1, 0xf2, 63, 0xf4]);
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "\"AfI/9A==\"");
+        assert_eq!(hr_deserialize::<ArrayStructPadded>(&val_str).unwrap(), val);
+
+        let val = ArrayStructUrlSafePadded([1, 0xf2, 63, 0xf4]);
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "\"AfI_9A==\"");
+        assert_eq!(
+            hr_deserialize::<ArrayStructUrlSafePadded>(&val_str).unwrap(),
+            val
+        );
+    }
+
+    #[test]
+    fn any_padding() {
+        let val = ArrayStructAnyPadding([1, 0xf2, 63, 0xf4]);
+        assert_eq!(hr_serialize(&val).unwrap(), "\"AfI/9A\"");
+        for string in ["\"AfI/9A\"", "\"AfI/9A==\""] {
+            assert_eq!(
+                hr_deserialize::<ArrayStructAnyPadding>(string).unwrap(),
+                val
+            );
+        }
+
+        let val = ArrayStructUrlSafeAnyPadding([1, 0xf2, 63, 0xf4]);
+        assert_eq!(hr_serialize(&val).unwrap(), "\"AfI_9A\"");
+        for string in ["\"AfI_9A\"", "\"AfI_9A==\""] {
+            assert_eq!(
+                hr_deserialize::<ArrayStructUrlSafeAnyPadding>(string).unwrap(),
+                val
+            );
+        }
+    }
+
+    #[test]
+    fn padding_errors() {
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>("\"AfI/9A==\"").unwrap_err(),
+            "invalid value: string \"AfI/9A==\", expected valid padding at line 1 column 10"
+        );
+        assert_eq!(
+            hr_deserialize::<ArrayStructPadded>("\"AfI/9A\"").unwrap_err(),
+            "invalid value: string \"AfI/9A\", expected valid padding at line 1 column 8"
+        );
+    }
+
+    #[test]
+    fn padding_at_chunk_boundary() {
+        // The padding ends the first 256-character chunk the input is decoded in
+        let padded = ["AAAA".repeat(63).as_str(), "AA=="].concat();
+        assert_eq!(Base64Padded::decode(&padded).unwrap().len(), 190);
+
+        let string = [padded.as_str(), "AAAA"].concat();
+        let expected = DecodeError::InvalidCharacter {
+            character: '=',
+            offset: 254,
+        };
+        let mut buffer = [0u8; 256];
+        assert_eq!(
+            Base64Padded::decode_into(&string, &mut buffer),
+            Err(expected)
+        );
+        assert_eq!(Base64Padded::decode_into(&string, &mut []), Err(expected));
+        assert_eq!(
+            Base64AnyPadding::decode_into(&string, &mut buffer),
+            Err(expected)
+        );
+        assert_eq!(
+            Base64AnyPadding::decode_into(&string, &mut []),
+            Err(expected)
+        );
+    }
+
+    #[test]
+    fn roundtrip_wrapped() {
+        let val = ArrayStructWrapped([1, 0xf2, 63, 0xf4, 5, 6, 7, 8, 9, 10]);
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "\"AfI/\\n9AUG\\nBwgJ\\nCg==\"");
+        assert_eq!(hr_deserialize::<ArrayStructWrapped>(&val_str).unwrap(), val);
+
+        // Any whitespace is ignored, and the padding is optional
+        assert_eq!(
+            hr_deserialize::<ArrayStructWrapped>("\" AfI/9A\\r\\nUGBw gJ\\tCg\\n\"").unwrap(),
+            val
+        );
+
+        // No wrapping
+        assert_eq!(Base64Wrapped::<0>::encode(&[1, 0xf2, 63, 0xf4]), "AfI/9A==");
+    }
+
+    #[test]
+    fn roundtrip_mime_toml() {
+        // Longer than the intermediate buffer used in decoding
+        let mut value = [0u8; 256];
+        for (i, byte) in value.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let val = LongArrayStructMime { value };
+
+        let encoded = Base64Mime::encode(&value);
+        let lines = encoded.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 5);
+        assert!(lines.iter().take(4).all(|line| line.len() == 76));
+        assert_eq!(lines.last().unwrap().len(), 344 - 4 * 76);
+
+        let val_str = toml::to_string(&val).unwrap();
+        assert!(val_str.starts_with("value = \"\"\"\n"));
+        assert_eq!(
+            toml::from_str::<LongArrayStructMime>(&val_str).unwrap(),
+            val
+        );
+    }
+
+    #[test]
+    fn errors_wrapped() {
+        assert_eq!(
+            hr_deserialize::<ArrayStructWrapped>("\"AfI/\\n9AUG\\nBwgJ\\nCgA=\"").unwrap_err(),
+            "Expected a bytestring of length 10, got 11 at line 1 column 24"
+        );
+        assert_eq!(
+            hr_deserialize::<ArrayStructWrapped>("\"AfI_\\n9AUG\\nBwgJ\\nCg==\"").unwrap_err(),
+            concat![
+                "invalid value: string \"AfI_\\n9AUG\\nBwgJ\\nCg==\", ",
+                "expected a valid character at offset 3 at line 1 column 24"
+            ]
+        );
+        // The offset accounts for the line breaks
+        assert_eq!(
+            hr_deserialize::<ArrayStructWrapped>("\"AfI/\\n9A_G\\nBwgJ\\nCg==\"").unwrap_err(),
+            concat![
+                "invalid value: string \"AfI/\\n9A_G\\nBwgJ\\nCg==\", ",
+                "expected a valid character at offset 7 at line 1 column 24"
+            ]
+        );
+    }
+
+    #[test]
+    fn padding_at_chunk_boundary_wrapped() {
+        // The padding ends the first 256-character chunk (not counting the line breaks),
+        // and is split by a line break
+        let line = "AAAA".repeat(19);
+        let string = [
+            &line,
+            "\n",
+            &line,
+            "\n",
+            &line,
+            "\n",
+            "AAAAAAAAAAAAAAAAAAAAAAAAAA=\n=AAAA",
+        ]
+        .concat();
+        let expected = DecodeError::InvalidCharacter {
+            character: '=',
+            offset: 257,
+        };
+        let mut buffer = [0u8; 256];
+        assert_eq!(Base64Mime::decode_into(&string, &mut buffer), Err(expected));
+        assert_eq!(Base64Mime::decode_into(&string, &mut []), Err(expected));
+    }
+
+    #[test]
+    fn errors() {
+        // Wrong length
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>("\"AfI/9AE\"").unwrap_err(),
+            "Expected a bytestring of length 4, got 5 at line 1 column 9"
+        );
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>("\"AfI/\"").unwrap_err(),
+            "Expected a bytestring of length 4, got 3 at line 1 column 6"
+        );
+
+        // A character outside of the alphabet
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>("\"AfI_9A\"").unwrap_err(),
+            "invalid value: string \"AfI_9A\", expected a valid character at offset 3 at line 1 column 8"
+        );
+        // An invalid character is reported even if the string is too long for the target
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>("\"AfI/9AE_\"").unwrap_err(),
+            "invalid value: string \"AfI/9AE_\", expected a valid character at offset 7 at line 1 column 10"
+        );
+    }
 }
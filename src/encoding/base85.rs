@@ -0,0 +1,410 @@
+use core::{fmt, str};
+
+use super::{DecodeError, EncodeError, Encoding};
+
+const INVALID: u8 = 0xff;
+
+struct Alphabet {
+    chars: [u8; 85],
+    values: [u8; 256],
+}
+
+impl Alphabet {
+    #[allow(clippy::indexing_slicing)]
+    const fn new(chars: &[u8; 85]) -> Self {
+        let mut values = [INVALID; 256];
+        let mut i = 0;
+        while i < 85 {
+            values[chars[i] as usize] = i as u8;
+            i += 1;
+        }
+        Self {
+            chars: *chars,
+            values,
+        }
+    }
+
+    fn encode_group(&self, group: [u8; 4]) -> [u8; 5] {
+        let mut value = u32::from_be_bytes(group);
+        let mut digits = [0u8; 5];
+        for digit in digits.iter_mut().rev() {
+            // `value % 85` is always a valid index in `chars`
+            *digit = self
+                .chars
+                .get((value % 85) as usize)
+                .copied()
+                .unwrap_or(INVALID);
+            value /= 85;
+        }
+        digits
+    }
+
+    fn value(&self, c: char) -> Option<u32> {
+        let value = *self.values.get(c as usize)?;
+        (value != INVALID).then_some(u32::from(value))
+    }
+}
+
+struct Variant {
+    alphabet: Alphabet,
+    /// Whether a group of four zero bytes is encoded as `z`.
+    abbreviate_zeros: bool,
+    /// Whether a trailing partial group is allowed.
+    allow_partial: bool,
+    /// Whether whitespace is ignored on decoding.
+    skip_whitespace: bool,
+}
+
+const ASCII85_VARIANT: Variant = Variant {
+    alphabet: Alphabet::new(
+        b"!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstu",
+    ),
+    abbreviate_zeros: true,
+    allow_partial: true,
+    skip_whitespace: true,
+};
+
+const Z85_VARIANT: Variant = Variant {
+    alphabet: Alphabet::new(
+        b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#",
+    ),
+    abbreviate_zeros: false,
+    allow_partial: false,
+    skip_whitespace: false,
+};
+
+const RFC1924_VARIANT: Variant = Variant {
+    alphabet: Alphabet::new(
+        b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~",
+    ),
+    abbreviate_zeros: false,
+    allow_partial: true,
+    skip_whitespace: false,
+};
+
+fn write_digits<W: fmt::Write>(digits: &[u8], writer: &mut W) -> fmt::Result {
+    // The alphabets only contain ASCII characters
+    writer.write_str(str::from_utf8(digits).map_err(|_| fmt::Error)?)
+}
+
+fn encode_to<W: fmt::Write>(variant: &Variant, bytes: &[u8], writer: &mut W) -> fmt::Result {
+    let alphabet = &variant.alphabet;
+    let mut chunks = bytes.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut group = [0u8; 4];
+        group.copy_from_slice(chunk);
+        if variant.abbreviate_zeros && group == [0u8; 4] {
+            writer.write_char('z')?;
+        } else {
+            write_digits(&alphabet.encode_group(group), writer)?;
+        }
+    }
+
+    // A partial group is padded with zeros, and only the necessary digits are written.
+    let remainder = chunks.remainder();
+    if !remainder.is_empty() {
+        let mut group = [0u8; 4];
+        for (dst, src) in group.iter_mut().zip(remainder) {
+            *dst = *src;
+        }
+        let digits = alphabet.encode_group(group);
+        write_digits(digits.get(..=remainder.len()).ok_or(fmt::Error)?, writer)?;
+    }
+
+    Ok(())
+}
+
+/// Writes the bytes into a slice while they fit, and counts the total length.
+struct Output<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+}
+
+impl Output<'_> {
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        if let Some(dest) = self.buffer.get_mut(self.len..self.len + bytes.len()) {
+            dest.copy_from_slice(bytes);
+        }
+        self.len += bytes.len();
+    }
+}
+
+fn decode(variant: &Variant, string: &str, result: &mut Output<'_>) -> Result<(), DecodeError> {
+    let mut value = 0u32;
+    let mut digits = 0;
+    let mut last_position = 0;
+
+    for (position, c) in string.char_indices() {
+        if variant.skip_whitespace && c.is_ascii_whitespace() {
+            continue;
+        }
+
+        if variant.abbreviate_zeros && c == 'z' {
+            if digits != 0 {
+                return Err(DecodeError::InvalidCharacter {
+                    character: c,
+                    offset: position,
+                });
+            }
+            result.write_bytes(&[0u8; 4]);
+            continue;
+        }
+
+        let digit = variant
+            .alphabet
+            .value(c)
+            .ok_or(DecodeError::InvalidCharacter {
+                character: c,
+                offset: position,
+            })?;
+        // A group overflowing 4 bytes is reported at the digit where it happened.
+        value = value
+            .checked_mul(85)
+            .and_then(|value| value.checked_add(digit))
+            .ok_or(DecodeError::InvalidCharacter {
+                character: c,
+                offset: position,
+            })?;
+        digits += 1;
+        last_position = position;
+
+        if digits == 5 {
+            result.write_bytes(&value.to_be_bytes());
+            value = 0;
+            digits = 0;
+        }
+    }
+
+    if digits == 0 {
+        return Ok(());
+    }
+
+    if !variant.allow_partial || digits == 1 {
+        return Err(DecodeError::InvalidLength {
+            length: digits,
+            expected: if variant.allow_partial {
+                "a trailing group of 2 to 5 characters"
+            } else {
+                "a trailing group of 5 characters"
+            },
+        });
+    }
+
+    // A partial group is padded with the highest digit,
+    // and only the bytes corresponding to the given digits are kept.
+    for _ in digits..5 {
+        value = value
+            .checked_mul(85)
+            .and_then(|value| value.checked_add(84))
+            .ok_or_else(|| DecodeError::invalid_character(string, last_position))?;
+    }
+    let bytes = value.to_be_bytes();
+    result.write_bytes(bytes.get(..digits - 1).unwrap_or(&bytes));
+
+    Ok(())
+}
+
+fn decode_into(variant: &Variant, string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+    let mut result = Output { buffer, len: 0 };
+    decode(variant, string, &mut result)?;
+    Ok(result.len)
+}
+
+fn strip_delimiters(string: &str) -> Result<&str, DecodeError> {
+    string
+        .strip_prefix("<~")
+        .ok_or(DecodeError::MissingPrefix { prefix: "<~" })?
+        .strip_suffix("~>")
+        .ok_or(DecodeError::MissingSuffix { suffix: "~>" })
+}
+
+/// Encodes the byte sequence into an Ascii85 representation.
+///
+/// Uses the Adobe variant: the encoded string is delimited by `<~` and `~>`,
+/// groups of four zero bytes are abbreviated as `z`, and whitespace is ignored on decoding.
+///
+/// Note that the alphabet includes `"` and `\`, which require escaping in JSON strings;
+/// consider using [`Z85`] or [`Base85`] instead for JSON.
+pub struct Ascii85;
+
+impl Encoding for Ascii85 {
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        writer.write_str("<~")?;
+        encode_to(&ASCII85_VARIANT, bytes, writer)?;
+        writer.write_str("~>")
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_into(&ASCII85_VARIANT, strip_delimiters(string)?, buffer)
+            .map_err(|err| err.shifted(2))
+    }
+}
+
+/// Encodes the byte sequence into a Z85 representation (as defined by ZeroMQ RFC 32).
+///
+/// The length of the byte sequence must be a multiple of 4;
+/// serializing a byte sequence of a different length results in an error,
+/// and [`Encoding::encode`] panics.
+pub struct Z85;
+
+impl Encoding for Z85 {
+    fn check_encodable(bytes: &[u8]) -> Result<(), EncodeError> {
+        if bytes.len() % 4 != 0 {
+            return Err(EncodeError::InvalidLength {
+                encoding: "Z85",
+                length: bytes.len(),
+                expected: "a multiple of 4",
+            });
+        }
+        Ok(())
+    }
+
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        if bytes.len() % 4 != 0 {
+            return Err(fmt::Error);
+        }
+        encode_to(&Z85_VARIANT, bytes, writer)
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_into(&Z85_VARIANT, string, buffer)
+    }
+}
+
+/// Encodes the byte sequence into a base85 representation using the RFC 1924 alphabet.
+///
+/// Trailing partial groups are encoded without padding,
+/// which is compatible with `git` binary patches and Python's `base64.b85encode()`.
+pub struct Base85;
+
+impl Encoding for Base85 {
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        encode_to(&RFC1924_VARIANT, bytes, writer)
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_into(&RFC1924_VARIANT, string, buffer)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::{Ascii85, Base85, Z85};
+    use crate::{
+        tests::common::{hr_deserialize, hr_serialize},
+        ArrayLike, SliceLike,
+    };
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Ascii85Struct(#[serde(with = "ArrayLike::<Ascii85>")] [u8; 7]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Z85Struct(#[serde(with = "ArrayLike::<Z85>")] [u8; 8]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Z85SliceStruct(#[serde(with = "SliceLike::<Z85>")] [u8; 7]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Base85Struct(#[serde(with = "ArrayLike::<Base85>")] [u8; 7]);
+
+    #[test]
+    fn roundtrip_ascii85() {
+        let val = Ascii85Struct([0, 0, 0, 0, 1, 0xf2, 63]);
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "\"<~z!V/G~>\"");
+        let val_back = hr_deserialize::<Ascii85Struct>(&val_str).unwrap();
+        assert_eq!(val, val_back);
+
+        // Whitespace is ignored
+        let val_back = hr_deserialize::<Ascii85Struct>("\"<~z !V\\n/G~>\"").unwrap();
+        assert_eq!(val, val_back);
+    }
+
+    #[test]
+    fn roundtrip_z85() {
+        // The example from the Z85 spec
+        let val = Z85Struct([0x86, 0x4F, 0xD2, 0x6F, 0xB5, 0x59, 0xF7, 0x5B]);
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "\"HelloWorld\"");
+        let val_back = hr_deserialize::<Z85Struct>(&val_str).unwrap();
+        assert_eq!(val, val_back);
+    }
+
+    #[test]
+    fn roundtrip_base85() {
+        let val = Base85Struct([1, 0xf2, 63, 0xf4, 1, 0xf2, 63]);
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "\"0rEfe0rEc\"");
+        let val_back = hr_deserialize::<Base85Struct>(&val_str).unwrap();
+        assert_eq!(val, val_back);
+    }
+
+    #[test]
+    fn errors() {
+        // Z85 requires the length to be a multiple of 4
+        assert_eq!(
+            hr_serialize(Z85SliceStruct([1, 2, 3, 4, 5, 6, 7])).unwrap_err(),
+            "Z85 requires the length of the byte sequence to be a multiple of 4, got 7"
+        );
+
+        // ... and the length of the encoded string to be a multiple of 5
+        assert_eq!(
+            hr_deserialize::<Z85Struct>("\"HelloWorl\"").unwrap_err(),
+            "invalid length 4, expected a trailing group of 5 characters at line 1 column 11"
+        );
+
+        // A character outside of the alphabet
+        assert_eq!(
+            hr_deserialize::<Base85Struct>("\"0rEfe0r\\\"c\"").unwrap_err(),
+            concat![
+                "invalid value: string \"0rEfe0r\\\"c\", ",
+                "expected a valid character at offset 7 at line 1 column 12"
+            ]
+        );
+
+        // A single trailing character cannot encode any bytes
+        assert_eq!(
+            hr_deserialize::<Base85Struct>("\"0rEfe0\"").unwrap_err(),
+            "invalid length 1, expected a trailing group of 2 to 5 characters at line 1 column 8"
+        );
+
+        // A group that does not fit into `u32`
+        assert_eq!(
+            hr_deserialize::<Base85Struct>("\"~~~~~\"").unwrap_err(),
+            concat![
+                "invalid value: string \"~~~~~\", ",
+                "expected a valid character at offset 4 at line 1 column 7"
+            ]
+        );
+
+        // `z` inside a group
+        assert_eq!(
+            hr_deserialize::<Ascii85Struct>("\"<~!Vz~>\"").unwrap_err(),
+            concat![
+                "invalid value: string \"<~!Vz~>\", ",
+                "expected a valid character at offset 4 at line 1 column 9"
+            ]
+        );
+
+        // Missing delimiters
+        assert_eq!(
+            hr_deserialize::<Ascii85Struct>("\"z!V/G\"").unwrap_err(),
+            concat![
+                "invalid value: string \"z!V/G\", ",
+                "expected a string starting with `<~` at line 1 column 7"
+            ]
+        );
+        assert_eq!(
+            hr_deserialize::<Ascii85Struct>("\"<~z!V/G\"").unwrap_err(),
+            concat![
+                "invalid value: string \"<~z!V/G\", ",
+                "expected a string ending with `~>` at line 1 column 9"
+            ]
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use core::{fmt, marker::PhantomData};
+
+use bech32::{
+    primitives::{
+        decode::{
+            CharError, CheckedHrpstring, CheckedHrpstringError, ChecksumError,
+            UncheckedHrpstringError,
+        },
+        iter::{ByteIterExt, Fe32IterExt},
+    },
+    Checksum, Hrp,
+};
+
+use super::{DecodeError, Encoding};
+
+/// A marker type providing the human-readable part (HRP) for [`Bech32`] and [`Bech32m`] encodings.
+///
+/// ```
+/// use serde_encoded_bytes::Bech32Hrp;
+///
+/// struct Npub;
+///
+/// impl Bech32Hrp for Npub {
+///     const HRP: &'static str = "npub";
+/// }
+/// ```
+pub trait Bech32Hrp {
+    /// The human-readable part.
+    ///
+    /// Must be a valid bech32 HRP (1 to 83 ASCII characters in the range 33-126),
+    /// otherwise the encoding will panic.
+    const HRP: &'static str;
+}
+
+fn encode_to<Ck: Checksum, W: fmt::Write>(hrp: &str, bytes: &[u8], writer: &mut W) -> fmt::Result {
+    let hrp = Hrp::parse_unchecked(hrp);
+    for c in bytes
+        .iter()
+        .copied()
+        .bytes_to_fes()
+        .with_checksum::<Ck>(&hrp)
+        .chars()
+    {
+        writer.write_char(c)?;
+    }
+    Ok(())
+}
+
+/// Returns the offset of the first character in the data part (after the last separator)
+/// matching the predicate.
+fn find_in_data(string: &str, predicate: impl Fn(char) -> bool) -> usize {
+    let data_start = string.rfind('1').map_or(0, |separator| separator + 1);
+    string
+        .get(data_start..)
+        .and_then(|data| data.find(predicate))
+        .map_or(data_start, |offset| data_start + offset)
+}
+
+fn decode_error(hrp: &'static str, string: &str, err: CheckedHrpstringError) -> DecodeError {
+    let data_len = string
+        .rfind('1')
+        .map_or(0, |separator| string.len() - separator - 1);
+    match err {
+        CheckedHrpstringError::Parse(UncheckedHrpstringError::Char(err)) => match err {
+            CharError::InvalidChar(c) => {
+                DecodeError::invalid_character(string, find_in_data(string, |x| x == c))
+            }
+            CharError::MixedCase => {
+                // The case of the first letter is taken as the expected one
+                let lowercase = string
+                    .chars()
+                    .find(char::is_ascii_alphabetic)
+                    .is_some_and(|c| c.is_ascii_lowercase());
+                let offset = string
+                    .find(|c: char| c.is_ascii_alphabetic() && c.is_ascii_lowercase() != lowercase)
+                    .unwrap_or_default();
+                DecodeError::invalid_character(string, offset)
+            }
+            CharError::NothingAfterSeparator => DecodeError::InvalidLength {
+                length: 0,
+                expected: "a data part of at least 6 characters",
+            },
+            _ => DecodeError::MissingPrefix { prefix: hrp },
+        },
+        CheckedHrpstringError::Checksum(ChecksumError::InvalidResidue) => {
+            DecodeError::ChecksumMismatch
+        }
+        CheckedHrpstringError::Checksum(ChecksumError::CodeLength(_)) => {
+            DecodeError::InvalidLength {
+                length: string.len(),
+                expected: "at most 1023 characters",
+            }
+        }
+        CheckedHrpstringError::Checksum(_) => DecodeError::InvalidLength {
+            length: data_len,
+            expected: "a data part of at least 6 characters",
+        },
+        // Invalid human-readable part
+        _ => DecodeError::MissingPrefix { prefix: hrp },
+    }
+}
+
+fn decode_into<Ck: Checksum>(
+    hrp: &'static str,
+    string: &str,
+    buffer: &mut [u8],
+) -> Result<usize, DecodeError> {
+    let checked =
+        CheckedHrpstring::new::<Ck>(string).map_err(|err| decode_error(hrp, string, err))?;
+    if checked.hrp() != Hrp::parse_unchecked(hrp) {
+        return Err(DecodeError::MissingPrefix { prefix: hrp });
+    }
+
+    let mut len = 0;
+    for byte in checked.byte_iter() {
+        if let Some(dest) = buffer.get_mut(len) {
+            *dest = byte;
+        }
+        len += 1;
+    }
+    Ok(len)
+}
+
+/// Encodes the byte sequence into a bech32 representation (as defined in BIP-173)
+/// with the human-readable part provided by `H`.
+///
+/// Note that the checksum is only guaranteed to detect errors for strings of up to 90 characters,
+/// and strings longer than 1023 characters will be rejected on decoding.
+pub struct Bech32<H: Bech32Hrp>(PhantomData<H>);
+
+impl<H: Bech32Hrp> Encoding for Bech32<H> {
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        encode_to::<bech32::Bech32, W>(H::HRP, bytes, writer)
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_into::<bech32::Bech32>(H::HRP, string, buffer)
+    }
+}
+
+/// Encodes the byte sequence into a bech32m representation (as defined in BIP-350)
+/// with the human-readable part provided by `H`.
+///
+/// Note that the checksum is only guaranteed to detect errors for strings of up to 90 characters,
+/// and strings longer than 1023 characters will be rejected on decoding.
+pub struct Bech32m<H: Bech32Hrp>(PhantomData<H>);
+
+impl<H: Bech32Hrp> Encoding for Bech32m<H> {
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        encode_to::<bech32::Bech32m, W>(H::HRP, bytes, writer)
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_into::<bech32::Bech32m>(H::HRP, string, buffer)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::{Bech32, Bech32Hrp, Bech32m};
+    use crate::{
+        tests::common::{hr_deserialize, hr_serialize},
+        ArrayLike,
+    };
+
+    struct TestHrp;
+
+    impl Bech32Hrp for TestHrp {
+        const HRP: &'static str = "test";
+    }
+
+    struct OtherHrp;
+
+    impl Bech32Hrp for OtherHrp {
+        const HRP: &'static str = "other";
+    }
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStruct(#[serde(with = "ArrayLike::<Bech32<TestHrp>>")] [u8; 4]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStructM(#[serde(with = "ArrayLike::<Bech32m<TestHrp>>")] [u8; 4]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStructOtherHrp(#[serde(with = "ArrayLike::<Bech32m<OtherHrp>>")] [u8; 4]);
+
+    #[test]
+    fn roundtrip() {
+        let val = ArrayStruct([1, 0xf2, 63, 0xf4]);
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "\"test1q8erlaqwhptgx\"");
+        let val_back = hr_deserialize::<ArrayStruct>(&val_str).unwrap();
+        assert_eq!(val, val_back);
+    }
+
+    #[test]
+    fn roundtrip_m() {
+        let val = ArrayStructM([1, 0xf2, 63, 0xf4]);
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "\"test1q8erlaqmt38dy\"");
+        let val_back = hr_deserialize::<ArrayStructM>(&val_str).unwrap();
+        assert_eq!(val, val_back);
+    }
+
+    #[test]
+    fn errors() {
+        let val_str = hr_serialize(ArrayStructM([1, 0xf2, 63, 0xf4])).unwrap();
+
+        // Mismatching HRP
+        assert_eq!(
+            hr_deserialize::<ArrayStructOtherHrp>(&val_str).unwrap_err(),
+            concat![
+                "invalid value: string \"test1q8erlaqmt38dy\", ",
+                "expected a string starting with `other` at line 1 column 20"
+            ]
+        );
+
+        // A bech32m string is not a valid bech32 string
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>(&val_str).unwrap_err(),
+            concat![
+                "invalid value: string \"test1q8erlaqmt38dy\", ",
+                "expected a valid checksum at line 1 column 20"
+            ]
+        );
+
+        // Missing separator
+        assert_eq!(
+            hr_deserialize::<ArrayStructM>("\"test\"").unwrap_err(),
+            "invalid value: string \"test\", expected a string starting with `test` at line 1 column 6"
+        );
+
+        // A character outside of the alphabet (`b` is not used in bech32)
+        assert_eq!(
+            hr_deserialize::<ArrayStructM>("\"test1q8erlaqmt38db\"").unwrap_err(),
+            concat![
+                "invalid value: string \"test1q8erlaqmt38db\", ",
+                "expected a valid character at offset 17 at line 1 column 20"
+            ]
+        );
+
+        // Mixed case
+        assert_eq!(
+            hr_deserialize::<ArrayStructM>("\"test1q8erlaqMt38dy\"").unwrap_err(),
+            concat![
+                "invalid value: string \"test1q8erlaqMt38dy\", ",
+                "expected a valid character at offset 12 at line 1 column 20"
+            ]
+        );
+    }
+}
@@ -0,0 +1,262 @@
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use super::{DecodeError, EncodeError, Encoding};
+
+/// An object-safe counterpart of [`Encoding`], allowing the encoding to be selected at runtime.
+///
+/// Implemented for every [`Encoding`] implementor, and for [`EncodingKind`].
+pub trait DynEncoding {
+    /// Same as [`Encoding::ACCEPTS_SEQUENCES`].
+    fn dyn_accepts_sequences(&self) -> bool;
+
+    /// Same as [`Encoding::check_encodable`].
+    fn dyn_check_encodable(&self, bytes: &[u8]) -> Result<(), EncodeError>;
+
+    /// Same as [`Encoding::encode_to`].
+    fn dyn_encode_to(&self, bytes: &[u8], writer: &mut dyn fmt::Write) -> fmt::Result;
+
+    /// Same as [`Encoding::decode_into`].
+    fn dyn_decode_into(&self, string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError>;
+
+    /// Same as [`Encoding::encode`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`dyn_encode_to`](`Self::dyn_encode_to`) fails.
+    #[cfg(feature = "alloc")]
+    fn dyn_encode(&self, bytes: &[u8]) -> String {
+        let mut result = String::new();
+        self.dyn_encode_to(bytes, &mut result)
+            .expect("the byte sequence should satisfy `Encoding::check_encodable()`");
+        result
+    }
+
+    /// Same as [`Encoding::decode`].
+    #[cfg(feature = "alloc")]
+    fn dyn_decode(&self, string: &str) -> Result<Vec<u8>, DecodeError> {
+        let len = self.dyn_decode_into(string, &mut [])?;
+        let mut bytes = alloc::vec![0u8; len];
+        self.dyn_decode_into(string, &mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+impl<Enc: Encoding> DynEncoding for Enc {
+    fn dyn_accepts_sequences(&self) -> bool {
+        Enc::ACCEPTS_SEQUENCES
+    }
+
+    fn dyn_check_encodable(&self, bytes: &[u8]) -> Result<(), EncodeError> {
+        Enc::check_encodable(bytes)
+    }
+
+    fn dyn_encode_to(&self, bytes: &[u8], mut writer: &mut dyn fmt::Write) -> fmt::Result {
+        Enc::encode_to(bytes, &mut writer)
+    }
+
+    fn dyn_decode_into(&self, string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        Enc::decode_into(string, buffer)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn dyn_encode(&self, bytes: &[u8]) -> String {
+        Enc::encode(bytes)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn dyn_decode(&self, string: &str) -> Result<Vec<u8>, DecodeError> {
+        Enc::decode(string)
+    }
+}
+
+/// An encoding selected at runtime, e.g. from a command-line flag or a configuration option.
+///
+/// The available variants depend on the enabled features.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EncodingKind {
+    /// [`Hex`](`super::Hex`).
+    #[cfg(any(feature = "hex", test))]
+    Hex,
+    /// [`HexUpper`](`super::HexUpper`).
+    #[cfg(any(feature = "hex", test))]
+    HexUpper,
+    /// [`HexNoPrefix`](`super::HexNoPrefix`).
+    #[cfg(any(feature = "hex", test))]
+    HexNoPrefix,
+    /// [`HexNoPrefixUpper`](`super::HexNoPrefixUpper`).
+    #[cfg(any(feature = "hex", test))]
+    HexNoPrefixUpper,
+    /// [`HexOptionalPrefix`](`super::HexOptionalPrefix`).
+    #[cfg(any(feature = "hex", test))]
+    HexOptionalPrefix,
+    /// [`Base64`](`super::Base64`).
+    #[cfg(feature = "base64")]
+    Base64,
+    /// [`Base64Url`](`super::Base64Url`).
+    #[cfg(feature = "base64")]
+    Base64Url,
+    /// [`Base64Padded`](`super::Base64Padded`).
+    #[cfg(feature = "base64")]
+    Base64Padded,
+    /// [`Base64UrlPadded`](`super::Base64UrlPadded`).
+    #[cfg(feature = "base64")]
+    Base64UrlPadded,
+    /// [`Base64AnyPadding`](`super::Base64AnyPadding`).
+    #[cfg(feature = "base64")]
+    Base64AnyPadding,
+    /// [`Base64UrlAnyPadding`](`super::Base64UrlAnyPadding`).
+    #[cfg(feature = "base64")]
+    Base64UrlAnyPadding,
+    /// [`Base64Mime`](`super::Base64Mime`).
+    #[cfg(feature = "base64")]
+    Base64Mime,
+    /// [`Base32`](`super::Base32`).
+    #[cfg(feature = "base32")]
+    Base32,
+    /// [`Base32Hex`](`super::Base32Hex`).
+    #[cfg(feature = "base32")]
+    Base32Hex,
+    /// [`Base58`](`super::Base58`).
+    #[cfg(feature = "base58")]
+    Base58,
+    /// [`Base58Check`](`super::Base58Check`).
+    #[cfg(feature = "base58")]
+    Base58Check,
+    /// [`Ascii85`](`super::Ascii85`).
+    #[cfg(feature = "base85")]
+    Ascii85,
+    /// [`Z85`](`super::Z85`).
+    #[cfg(feature = "base85")]
+    Z85,
+    /// [`Base85`](`super::Base85`).
+    #[cfg(feature = "base85")]
+    Base85,
+    /// [`Eip55`](`super::Eip55`).
+    #[cfg(feature = "eip55")]
+    Eip55,
+}
+
+impl EncodingKind {
+    /// Returns the corresponding encoding object.
+    pub fn as_dyn(self) -> &'static dyn DynEncoding {
+        match self {
+            #[cfg(any(feature = "hex", test))]
+            Self::Hex => &super::Hex,
+            #[cfg(any(feature = "hex", test))]
+            Self::HexUpper => &super::HexUpper,
+            #[cfg(any(feature = "hex", test))]
+            Self::HexNoPrefix => &super::HexNoPrefix,
+            #[cfg(any(feature = "hex", test))]
+            Self::HexNoPrefixUpper => &super::HexNoPrefixUpper,
+            #[cfg(any(feature = "hex", test))]
+            Self::HexOptionalPrefix => &super::HexOptionalPrefix,
+            #[cfg(feature = "base64")]
+            Self::Base64 => &super::Base64,
+            #[cfg(feature = "base64")]
+            Self::Base64Url => &super::Base64Url,
+            #[cfg(feature = "base64")]
+            Self::Base64Padded => &super::Base64Padded,
+            #[cfg(feature = "base64")]
+            Self::Base64UrlPadded => &super::Base64UrlPadded,
+            #[cfg(feature = "base64")]
+            Self::Base64AnyPadding => &super::Base64AnyPadding,
+            #[cfg(feature = "base64")]
+            Self::Base64UrlAnyPadding => &super::Base64UrlAnyPadding,
+            #[cfg(feature = "base64")]
+            Self::Base64Mime => &super::Base64Wrapped::<76>,
+            #[cfg(feature = "base32")]
+            Self::Base32 => &super::Base32,
+            #[cfg(feature = "base32")]
+            Self::Base32Hex => &super::Base32Hex,
+            #[cfg(feature = "base58")]
+            Self::Base58 => &super::Base58,
+            #[cfg(feature = "base58")]
+            Self::Base58Check => &super::Base58Check,
+            #[cfg(feature = "base85")]
+            Self::Ascii85 => &super::Ascii85,
+            #[cfg(feature = "base85")]
+            Self::Z85 => &super::Z85,
+            #[cfg(feature = "base85")]
+            Self::Base85 => &super::Base85,
+            #[cfg(feature = "eip55")]
+            Self::Eip55 => &super::Eip55,
+        }
+    }
+}
+
+impl DynEncoding for EncodingKind {
+    fn dyn_accepts_sequences(&self) -> bool {
+        self.as_dyn().dyn_accepts_sequences()
+    }
+
+    fn dyn_check_encodable(&self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.as_dyn().dyn_check_encodable(bytes)
+    }
+
+    fn dyn_encode_to(&self, bytes: &[u8], writer: &mut dyn fmt::Write) -> fmt::Result {
+        self.as_dyn().dyn_encode_to(bytes, writer)
+    }
+
+    fn dyn_decode_into(&self, string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        self.as_dyn().dyn_decode_into(string, buffer)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn dyn_encode(&self, bytes: &[u8]) -> String {
+        self.as_dyn().dyn_encode(bytes)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn dyn_decode(&self, string: &str) -> Result<Vec<u8>, DecodeError> {
+        self.as_dyn().dyn_decode(string)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::vec;
+
+    #[cfg(feature = "base85")]
+    use super::EncodeError;
+    use super::{DecodeError, DynEncoding, EncodingKind};
+    use crate::encoding::Hex;
+
+    #[test]
+    fn dispatch() {
+        let encoding: &dyn DynEncoding = &EncodingKind::HexUpper;
+        assert_eq!(encoding.dyn_encode(&[1, 0xf2]), "0x01F2");
+        assert_eq!(encoding.dyn_decode("0x01F2").unwrap(), vec![1, 0xf2]);
+        assert!(!encoding.dyn_accepts_sequences());
+
+        let mut buffer = [0u8; 1];
+        assert_eq!(encoding.dyn_decode_into("0x01F2", &mut buffer).unwrap(), 2);
+
+        assert_eq!(
+            EncodingKind::Hex.dyn_decode("01f2").unwrap_err(),
+            DecodeError::MissingPrefix { prefix: "0x" }
+        );
+    }
+
+    #[test]
+    fn static_encodings() {
+        let encoding: &dyn DynEncoding = &Hex;
+        assert_eq!(encoding.dyn_encode(&[1, 0xf2]), "0x01f2");
+        assert!(encoding.dyn_check_encodable(&[1, 0xf2]).is_ok());
+    }
+
+    #[cfg(feature = "base85")]
+    #[test]
+    fn check_encodable() {
+        assert_eq!(
+            EncodingKind::Z85.dyn_check_encodable(&[1, 0xf2, 3]),
+            Err(EncodeError::InvalidLength {
+                encoding: "Z85",
+                length: 3,
+                expected: "a multiple of 4"
+            })
+        );
+    }
+}
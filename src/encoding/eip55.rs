@@ -0,0 +1,199 @@
+use core::{fmt, str};
+
+use tiny_keccak::{Hasher, Keccak};
+
+use super::{DecodeError, EncodeError, Encoding};
+
+const ADDRESS_LEN: usize = 20;
+
+/// Returns the Keccak-256 hash of the lowercase hex digits of the address.
+fn checksum_hash(lowercase_digits: &[u8; ADDRESS_LEN * 2]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    hasher.update(lowercase_digits);
+    let mut hash = [0u8; 32];
+    hasher.finalize(&mut hash);
+    hash
+}
+
+/// Returns `true` if the `i`-th digit (if it is a letter) must be in uppercase.
+fn is_uppercase_digit(hash: &[u8; 32], i: usize) -> bool {
+    let byte = hash.get(i / 2).copied().unwrap_or_default();
+    let nibble = if i % 2 == 0 { byte >> 4 } else { byte & 0xf };
+    nibble >= 8
+}
+
+/// Encodes a 20-byte address into a `0x`-prefixed hexadecimal representation
+/// with the mixed-case checksum defined in [EIP-55](https://eips.ethereum.org/EIPS/eip-55).
+///
+/// On decoding, the checksum is verified if the digits are in mixed case;
+/// all-lowercase and all-uppercase digits are accepted without verification.
+/// The `0x` prefix is required.
+///
+/// The length of the byte sequence must be 20;
+/// serializing a byte sequence of a different length results in an error,
+/// and [`Encoding::encode`] panics.
+pub struct Eip55;
+
+impl Encoding for Eip55 {
+    fn check_encodable(bytes: &[u8]) -> Result<(), EncodeError> {
+        if bytes.len() != ADDRESS_LEN {
+            return Err(EncodeError::InvalidLength {
+                encoding: "EIP-55",
+                length: bytes.len(),
+                expected: "20",
+            });
+        }
+        Ok(())
+    }
+
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        let mut digits = [0u8; ADDRESS_LEN * 2];
+        hex::encode_to_slice(bytes, &mut digits).map_err(|_| fmt::Error)?;
+        let hash = checksum_hash(&digits);
+        for (i, digit) in digits.iter_mut().enumerate() {
+            if is_uppercase_digit(&hash, i) {
+                digit.make_ascii_uppercase();
+            }
+        }
+        writer.write_str("0x")?;
+        writer.write_str(str::from_utf8(&digits).map_err(|_| fmt::Error)?)
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        let digits = string
+            .strip_prefix("0x")
+            .ok_or(DecodeError::MissingPrefix { prefix: "0x" })?;
+
+        let mut lowercase_digits = [0u8; ADDRESS_LEN * 2];
+        if digits.len() != lowercase_digits.len() {
+            return Err(DecodeError::InvalidLength {
+                length: digits.len(),
+                expected: "40 hex digits of an EIP-55 address",
+            });
+        }
+
+        let mut address = [0u8; ADDRESS_LEN];
+        hex::decode_to_slice(digits, &mut address).map_err(|err| match err {
+            hex::FromHexError::InvalidHexCharacter { index, .. } => {
+                DecodeError::invalid_character(string, index + 2)
+            }
+            // The length has been checked above
+            hex::FromHexError::OddLength | hex::FromHexError::InvalidStringLength => {
+                DecodeError::InvalidLength {
+                    length: digits.len(),
+                    expected: "40 hex digits of an EIP-55 address",
+                }
+            }
+        })?;
+
+        let has_lowercase = digits.bytes().any(|c| c.is_ascii_lowercase());
+        let has_uppercase = digits.bytes().any(|c| c.is_ascii_uppercase());
+        if has_lowercase && has_uppercase {
+            lowercase_digits.copy_from_slice(digits.as_bytes());
+            lowercase_digits.make_ascii_lowercase();
+            let hash = checksum_hash(&lowercase_digits);
+            let valid = digits.bytes().enumerate().all(|(i, c)| {
+                !c.is_ascii_alphabetic() || c.is_ascii_uppercase() == is_uppercase_digit(&hash, i)
+            });
+            if !valid {
+                return Err(DecodeError::ChecksumMismatch);
+            }
+        }
+
+        if let Some(dest) = buffer.get_mut(..ADDRESS_LEN) {
+            dest.copy_from_slice(&address);
+        }
+        Ok(ADDRESS_LEN)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::string::ToString;
+
+    use serde::{Deserialize, Serialize};
+
+    use super::Eip55;
+    use crate::{
+        tests::common::{hr_deserialize, hr_serialize},
+        ArrayLike,
+    };
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Address(#[serde(with = "ArrayLike::<Eip55>")] [u8; 20]);
+
+    // Test vectors from EIP-55
+    const CHECKSUMMED: [&str; 4] = [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ];
+
+    #[test]
+    fn roundtrip() {
+        for address in CHECKSUMMED {
+            let val = Address(
+                hex::decode(address.get(2..).unwrap())
+                    .unwrap()
+                    .try_into()
+                    .unwrap(),
+            );
+            let val_str = hr_serialize(&val).unwrap();
+            assert_eq!(val_str, ["\"", address, "\""].concat());
+            assert_eq!(hr_deserialize::<Address>(&val_str).unwrap(), val);
+        }
+    }
+
+    #[test]
+    fn single_case() {
+        let address = CHECKSUMMED.first().unwrap();
+        let expected = hex::decode(address.get(2..).unwrap()).unwrap();
+        for digits in [
+            address.get(2..).unwrap().to_lowercase(),
+            address.get(2..).unwrap().to_uppercase(),
+        ] {
+            let val_str = ["\"0x", &digits, "\""].concat();
+            let val = hr_deserialize::<Address>(&val_str).unwrap();
+            assert_eq!(val.0.as_slice(), expected);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        // One letter has the wrong case
+        assert_eq!(
+            hr_deserialize::<Address>("\"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD\"")
+                .unwrap_err(),
+            concat![
+                "invalid value: string \"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD\", ",
+                "expected a valid checksum at line 1 column 44"
+            ]
+        );
+        assert_eq!(
+            hr_deserialize::<Address>("\"5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed\"").unwrap_err(),
+            concat![
+                "invalid value: string \"5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed\", ",
+                "expected a string starting with `0x` at line 1 column 42"
+            ]
+        );
+        assert_eq!(
+            hr_deserialize::<Address>("\"0x5aAeb6\"").unwrap_err(),
+            "invalid length 6, expected 40 hex digits of an EIP-55 address at line 1 column 10"
+        );
+        assert_eq!(
+            hr_deserialize::<Address>("\"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg\"")
+                .unwrap_err(),
+            concat![
+                "invalid value: string \"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg\", ",
+                "expected a valid character at offset 41 at line 1 column 44"
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&crate::Encoded::<_, Eip55>::new([1u8; 4]))
+                .unwrap_err()
+                .to_string(),
+            "EIP-55 requires the length of the byte sequence to be 20, got 4"
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use core::fmt;
+
+/// An error returned by [`Encoding::decode_into`](`super::Encoding::decode_into`)
+/// and [`Encoding::decode`](`super::Encoding::decode`).
+///
+/// The offsets are in bytes, counted from the start of the whole string being decoded
+/// (including any prefix).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The string contains a character that is not allowed at this position.
+    InvalidCharacter {
+        /// The offending character.
+        character: char,
+        /// The offset of the character.
+        offset: usize,
+    },
+    /// The length of the string or of the encoded data is invalid.
+    InvalidLength {
+        /// The actual length.
+        length: usize,
+        /// The description of the expected length.
+        expected: &'static str,
+    },
+    /// The padding is malformed, missing, or not allowed.
+    InvalidPadding,
+    /// The string does not start with the required prefix.
+    MissingPrefix {
+        /// The required prefix.
+        prefix: &'static str,
+    },
+    /// The string does not end with the required suffix.
+    MissingSuffix {
+        /// The required suffix.
+        suffix: &'static str,
+    },
+    /// The checksum in the string does not match the data.
+    ChecksumMismatch,
+    /// The encoding of the string cannot be determined.
+    UnknownEncoding,
+}
+
+impl DecodeError {
+    /// Creates an [`InvalidCharacter`](`Self::InvalidCharacter`) error
+    /// for the character at `offset` in `string`.
+    // Unused if none of the encodings reporting characters are enabled.
+    #[allow(dead_code)]
+    pub(crate) fn invalid_character(string: &str, offset: usize) -> Self {
+        let character = string
+            .get(offset..)
+            .and_then(|tail| tail.chars().next())
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+        Self::InvalidCharacter { character, offset }
+    }
+
+    /// Shifts the offset (if any) by `shift`,
+    /// to be used when decoding a part of the string following a prefix.
+    // Unused if none of the encodings with prefixes are enabled.
+    #[allow(dead_code)]
+    pub(crate) fn shifted(self, shift: usize) -> Self {
+        match self {
+            Self::InvalidCharacter { character, offset } => Self::InvalidCharacter {
+                character,
+                offset: offset + shift,
+            },
+            _ => self,
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCharacter { character, offset } => {
+                write!(f, "invalid character {character:?} at offset {offset}")
+            }
+            Self::InvalidLength { length, expected } => {
+                write!(f, "invalid length {length}, expected {expected}")
+            }
+            Self::InvalidPadding => write!(f, "invalid padding"),
+            Self::MissingPrefix { prefix } => write!(f, "missing prefix `{prefix}`"),
+            Self::MissingSuffix { suffix } => write!(f, "missing suffix `{suffix}`"),
+            Self::ChecksumMismatch => write!(f, "checksum mismatch"),
+            Self::UnknownEncoding => write!(f, "unknown encoding"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// An error returned by [`Encoding::check_encodable`](`super::Encoding::check_encodable`)
+/// and [`DynEncoding::dyn_check_encodable`](`super::DynEncoding::dyn_check_encodable`).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeError {
+    /// The encoding does not support byte sequences of this length.
+    InvalidLength {
+        /// The name of the encoding.
+        encoding: &'static str,
+        /// The length of the byte sequence.
+        length: usize,
+        /// The description of the supported lengths.
+        expected: &'static str,
+    },
+    /// No encoding is selected (when `Scoped` is used outside of `with_encoding()`).
+    UnknownEncoding,
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength {
+                encoding,
+                length,
+                expected,
+            } => write!(
+                f,
+                "{encoding} requires the length of the byte sequence to be {expected}, got {length}"
+            ),
+            Self::UnknownEncoding => write!(
+                f,
+                "No encoding is selected for `Scoped`; use `with_encoding()` to select one"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EncodeError {}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::{DecodeError, EncodeError};
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            DecodeError::invalid_character("0x1g", 3).to_string(),
+            "invalid character 'g' at offset 3"
+        );
+        assert_eq!(
+            DecodeError::invalid_character("1g", 1)
+                .shifted(2)
+                .to_string(),
+            "invalid character 'g' at offset 3"
+        );
+        assert_eq!(
+            DecodeError::InvalidLength {
+                length: 3,
+                expected: "an even number of hex digits"
+            }
+            .to_string(),
+            "invalid length 3, expected an even number of hex digits"
+        );
+        assert_eq!(DecodeError::InvalidPadding.to_string(), "invalid padding");
+        assert_eq!(
+            DecodeError::MissingPrefix { prefix: "0x" }.to_string(),
+            "missing prefix `0x`"
+        );
+        assert_eq!(
+            DecodeError::MissingSuffix { suffix: "~>" }.to_string(),
+            "missing suffix `~>`"
+        );
+        assert_eq!(
+            DecodeError::ChecksumMismatch.to_string(),
+            "checksum mismatch"
+        );
+        assert_eq!(DecodeError::UnknownEncoding.to_string(), "unknown encoding");
+    }
+
+    #[test]
+    fn display_encode_error() {
+        assert_eq!(
+            EncodeError::InvalidLength {
+                encoding: "Z85",
+                length: 3,
+                expected: "a multiple of 4"
+            }
+            .to_string(),
+            "Z85 requires the length of the byte sequence to be a multiple of 4, got 3"
+        );
+    }
+}
@@ -1,47 +1,174 @@
-use alloc::{format, string::String, vec::Vec};
+use core::{fmt, str};
 
-use serde::de;
+use super::{DecodeError, Encoding};
 
-use super::Encoding;
+fn strip_prefix(string: &str) -> Result<&str, DecodeError> {
+    string
+        .strip_prefix("0x")
+        .ok_or(DecodeError::MissingPrefix { prefix: "0x" })
+}
+
+fn encode_to<W: fmt::Write>(
+    bytes: &[u8],
+    writer: &mut W,
+    prefix: &str,
+    uppercase: bool,
+) -> fmt::Result {
+    writer.write_str(prefix)?;
+    let mut buffer = [0u8; 128];
+    for chunk in bytes.chunks(buffer.len() / 2) {
+        let digits = buffer.get_mut(..chunk.len() * 2).ok_or(fmt::Error)?;
+        hex::encode_to_slice(chunk, digits).map_err(|_| fmt::Error)?;
+        if uppercase {
+            digits.make_ascii_uppercase();
+        }
+        writer.write_str(str::from_utf8(digits).map_err(|_| fmt::Error)?)?;
+    }
+    Ok(())
+}
+
+/// Decodes hex digits in either case (the prefix, if any, must be already stripped).
+fn decode_into(digits: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+    if digits.len() % 2 != 0 {
+        return Err(DecodeError::InvalidLength {
+            length: digits.len(),
+            expected: "an even number of hex digits",
+        });
+    }
+    let len = digits.len() / 2;
+    match buffer.get_mut(..len) {
+        Some(dest) => hex::decode_to_slice(digits, dest).map_err(|err| match err {
+            hex::FromHexError::InvalidHexCharacter { index, .. } => {
+                DecodeError::invalid_character(digits, index)
+            }
+            // The length has been checked above
+            hex::FromHexError::OddLength | hex::FromHexError::InvalidStringLength => {
+                DecodeError::InvalidLength {
+                    length: digits.len(),
+                    expected: "an even number of hex digits",
+                }
+            }
+        })?,
+        // Still validate the digits, so that the error does not depend on the buffer length
+        None => {
+            if let Some(index) = digits.bytes().position(|c| !c.is_ascii_hexdigit()) {
+                return Err(DecodeError::invalid_character(digits, index));
+            }
+        }
+    }
+    Ok(len)
+}
 
 /// Encodes the byte sequence into a `0x`-prefixed hexadecimal representation.
+///
+/// Lowercase digits are used for encoding; either case is accepted on decoding,
+/// but the lowercase `0x` prefix is required.
 pub struct Hex;
 
 impl Encoding for Hex {
-    fn encode(bytes: &[u8]) -> String {
-        format!("0x{}", hex::encode(bytes))
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        encode_to(bytes, writer, "0x", false)
     }
 
-    fn decode<E: de::Error>(string: &str) -> Result<Vec<u8>, E> {
-        let digits = string.strip_prefix("0x").ok_or_else(|| {
-            de::Error::invalid_value(
-                de::Unexpected::Str(string),
-                &"0x-prefixed hex-encoded bytes",
-            )
-        })?;
-        hex::decode(digits).map_err(de::Error::custom)
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_into(strip_prefix(string)?, buffer).map_err(|err| err.shifted(2))
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use alloc::string::{String, ToString};
+/// Encodes the byte sequence into a `0x`-prefixed hexadecimal representation
+/// with uppercase digits.
+///
+/// Decodes the same way as [`Hex`] does.
+pub struct HexUpper;
+
+impl Encoding for HexUpper {
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        encode_to(bytes, writer, "0x", true)
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_into(strip_prefix(string)?, buffer).map_err(|err| err.shifted(2))
+    }
+}
+
+/// Encodes the byte sequence into a hexadecimal representation without a prefix.
+///
+/// Lowercase digits are used for encoding; either case is accepted on decoding,
+/// and a prefix is not allowed.
+pub struct HexNoPrefix;
+
+impl Encoding for HexNoPrefix {
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        encode_to(bytes, writer, "", false)
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_into(string, buffer)
+    }
+}
+
+/// Encodes the byte sequence into a hexadecimal representation without a prefix
+/// with uppercase digits.
+///
+/// Decodes the same way as [`HexNoPrefix`] does.
+pub struct HexNoPrefixUpper;
 
+impl Encoding for HexNoPrefixUpper {
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        encode_to(bytes, writer, "", true)
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        decode_into(string, buffer)
+    }
+}
+
+/// Encodes the byte sequence the same way as [`Hex`] does,
+/// but accepts a `0x` prefix, a `0X` prefix, or no prefix at all on decoding,
+/// with digits in either case.
+pub struct HexOptionalPrefix;
+
+impl Encoding for HexOptionalPrefix {
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        Hex::encode_to(bytes, writer)
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        let digits = string
+            .strip_prefix("0x")
+            .or_else(|| string.strip_prefix("0X"))
+            .unwrap_or(string);
+        decode_into(digits, buffer).map_err(|err| err.shifted(string.len() - digits.len()))
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
     use serde::{Deserialize, Serialize};
 
-    use super::Hex;
-    use crate::ArrayLike;
+    use super::{Hex, HexNoPrefix, HexNoPrefixUpper, HexOptionalPrefix, HexUpper};
+    use crate::{
+        tests::common::{hr_deserialize, hr_serialize},
+        ArrayLike,
+    };
 
     #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
     struct ArrayStruct(#[serde(with = "ArrayLike::<Hex>")] [u8; 4]);
 
-    fn hr_serialize<T: Serialize>(value: T) -> Result<String, String> {
-        serde_json::to_string(&value).map_err(|err| err.to_string())
-    }
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStructUpper(#[serde(with = "ArrayLike::<HexUpper>")] [u8; 4]);
 
-    fn hr_deserialize<'de, T: Deserialize<'de>>(string: &'de str) -> Result<T, String> {
-        serde_json::from_str::<T>(string).map_err(|err| err.to_string())
-    }
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStructNoPrefix(#[serde(with = "ArrayLike::<HexNoPrefix>")] [u8; 4]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStructNoPrefixUpper(#[serde(with = "ArrayLike::<HexNoPrefixUpper>")] [u8; 4]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStructOptionalPrefix(#[serde(with = "ArrayLike::<HexOptionalPrefix>")] [u8; 4]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct LongArrayStruct(#[serde(with = "ArrayLike::<Hex>")] [u8; 256]);
 
     #[test]
     fn roundtrip() {
@@ -53,18 +180,124 @@ mod tests {
         assert_eq!(val, val_back);
     }
 
+    #[test]
+    fn roundtrip_variants() {
+        let bytes = [1, 0xf2, 3, 0xf4];
+
+        let val_str = hr_serialize(ArrayStructUpper(bytes)).unwrap();
+        assert_eq!(val_str, "\"0x01F203F4\"");
+        assert_eq!(
+            hr_deserialize::<ArrayStructUpper>(&val_str).unwrap(),
+            ArrayStructUpper(bytes)
+        );
+
+        let val_str = hr_serialize(ArrayStructNoPrefix(bytes)).unwrap();
+        assert_eq!(val_str, "\"01f203f4\"");
+        assert_eq!(
+            hr_deserialize::<ArrayStructNoPrefix>(&val_str).unwrap(),
+            ArrayStructNoPrefix(bytes)
+        );
+
+        let val_str = hr_serialize(ArrayStructNoPrefixUpper(bytes)).unwrap();
+        assert_eq!(val_str, "\"01F203F4\"");
+        assert_eq!(
+            hr_deserialize::<ArrayStructNoPrefixUpper>(&val_str).unwrap(),
+            ArrayStructNoPrefixUpper(bytes)
+        );
+
+        let val_str = hr_serialize(ArrayStructOptionalPrefix(bytes)).unwrap();
+        assert_eq!(val_str, "\"0x01f203f4\"");
+        assert_eq!(
+            hr_deserialize::<ArrayStructOptionalPrefix>(&val_str).unwrap(),
+            ArrayStructOptionalPrefix(bytes)
+        );
+    }
+
+    #[test]
+    fn case_insensitive_decoding() {
+        let bytes = [1, 0xf2, 3, 0xf4];
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>("\"0x01F203f4\"").unwrap(),
+            ArrayStruct(bytes)
+        );
+        assert_eq!(
+            hr_deserialize::<ArrayStructNoPrefix>("\"01F203F4\"").unwrap(),
+            ArrayStructNoPrefix(bytes)
+        );
+        for string in ["\"0x01f203f4\"", "\"0X01F203F4\"", "\"01f203F4\""] {
+            assert_eq!(
+                hr_deserialize::<ArrayStructOptionalPrefix>(string).unwrap(),
+                ArrayStructOptionalPrefix(bytes)
+            );
+        }
+    }
+
+    #[test]
+    fn roundtrip_long() {
+        // Longer than the intermediate buffer used in `encode_to()`
+        let mut bytes = [0u8; 256];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let val = LongArrayStruct(bytes);
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, ["\"0x", &hex::encode(bytes), "\""].concat());
+        assert_eq!(val_str.len(), 2 + 2 + 256 * 2);
+        let val_back = hr_deserialize::<LongArrayStruct>(&val_str).unwrap();
+        assert_eq!(val, val_back);
+    }
+
     #[test]
     fn errors() {
         assert_eq!(
             hr_deserialize::<ArrayStruct>("\"01f203f4\"").unwrap_err(),
             concat![
-                "invalid value: string \"01f203f4\", expected 0x-prefixed ",
-                "hex-encoded bytes at line 1 column 10"
+                "invalid value: string \"01f203f4\", expected a string starting with ",
+                "`0x` at line 1 column 10"
             ]
         );
         assert_eq!(
             hr_deserialize::<ArrayStruct>("\"0\"").unwrap_err(),
-            "invalid value: string \"0\", expected 0x-prefixed hex-encoded bytes at line 1 column 3"
+            "invalid value: string \"0\", expected a string starting with `0x` at line 1 column 3"
+        );
+        // An invalid digit is reported even if the string is too long for the target
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>("\"0x01f203f4g5\"").unwrap_err(),
+            concat![
+                "invalid value: string \"0x01f203f4g5\", ",
+                "expected a valid character at offset 10 at line 1 column 14"
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_variants() {
+        assert_eq!(
+            hr_deserialize::<ArrayStructUpper>("\"0X01F203F4\"").unwrap_err(),
+            concat![
+                "invalid value: string \"0X01F203F4\", expected a string starting with ",
+                "`0x` at line 1 column 12"
+            ]
+        );
+        assert_eq!(
+            hr_deserialize::<ArrayStructNoPrefix>("\"0x01f203\"").unwrap_err(),
+            concat![
+                "invalid value: string \"0x01f203\", ",
+                "expected a valid character at offset 1 at line 1 column 10"
+            ]
+        );
+        assert_eq!(
+            hr_deserialize::<ArrayStructOptionalPrefix>("\"0x01f203f\"").unwrap_err(),
+            "invalid length 7, expected an even number of hex digits at line 1 column 11"
+        );
+        // The offset includes the prefix
+        assert_eq!(
+            hr_deserialize::<ArrayStructOptionalPrefix>("\"0X01g203f4\"").unwrap_err(),
+            concat![
+                "invalid value: string \"0X01g203f4\", ",
+                "expected a valid character at offset 4 at line 1 column 12"
+            ]
         );
     }
 
@@ -80,8 +313,8 @@ mod tests {
         assert_eq!(
             hr_deserialize::<ArrayStruct>("\"\u{1D01}\"").unwrap_err(),
             concat![
-                "invalid value: string \"\u{1D01}\", expected 0x-prefixed ",
-                "hex-encoded bytes at line 1 column 5"
+                "invalid value: string \"\u{1D01}\", expected a string starting with ",
+                "`0x` at line 1 column 5"
             ]
         );
     }
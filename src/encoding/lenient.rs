@@ -0,0 +1,205 @@
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::{fmt, marker::PhantomData};
+
+use super::{DecodeError, EncodeError, Encoding};
+
+/// A wrapper for an encoding that additionally accepts sequences of integers on deserialization.
+///
+/// This is the representation `serde` uses for byte arrays by default,
+/// so this wrapper can be used to read the data serialized before switching to this crate.
+/// The lengths are enforced the same way as for bytestrings.
+/// Serialization is not affected.
+///
+/// Note that in human-readable formats this uses `Deserializer::deserialize_any()`,
+/// and therefore requires a self-describing format.
+pub struct Lenient<Enc>(PhantomData<Enc>);
+
+impl<Enc: Encoding> Encoding for Lenient<Enc> {
+    const ACCEPTS_SEQUENCES: bool = true;
+
+    fn check_encodable(bytes: &[u8]) -> Result<(), EncodeError> {
+        Enc::check_encodable(bytes)
+    }
+
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        Enc::encode_to(bytes, writer)
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        Enc::decode_into(string, buffer)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn encode(bytes: &[u8]) -> String {
+        Enc::encode(bytes)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn decode(string: &str) -> Result<Vec<u8>, DecodeError> {
+        Enc::decode(string)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use serde::{Deserialize, Serialize};
+
+    use super::Lenient;
+    use crate::{
+        tests::common::{
+            bin_deserialize, bin_serialize, hr_deserialize, hr_serialize, instantiation_message,
+        },
+        ArrayLike, BorrowedSliceLike, Hex, SliceLike,
+    };
+
+    #[cfg(feature = "generic-array-014")]
+    use crate::GenericArray014;
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct LegacyArray([u8; 4]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct LegacyVector(Vec<u8>);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStruct(#[serde(with = "ArrayLike::<Lenient<Hex>>")] [u8; 4]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct StrictArrayStruct(#[serde(with = "ArrayLike::<Hex>")] [u8; 4]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct VectorStruct(#[serde(with = "SliceLike::<Lenient<Hex>>")] Vec<u8>);
+
+    #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+    #[repr(transparent)]
+    struct Borrow([u8; 4]);
+
+    impl AsRef<[u8]> for Borrow {
+        fn as_ref(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    impl<'a> TryFrom<&'a [u8]> for &'a Borrow {
+        type Error = String;
+        fn try_from(source: &'a [u8]) -> Result<Self, Self::Error> {
+            if source.len() != 4 {
+                return Err("Wrong length".into());
+            }
+
+            Ok(unsafe { &*(source.as_ptr() as *const Borrow) })
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct BorrowStruct(#[serde(with = "BorrowedSliceLike::<Lenient<Hex>>")] Borrow);
+
+    #[cfg(feature = "generic-array-014")]
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct GenericArray014Struct(
+        #[serde(with = "GenericArray014::<Lenient<Hex>>")]
+        generic_array_014::GenericArray<u8, generic_array_014::typenum::U4>,
+    );
+
+    #[test]
+    fn serialization_not_affected() {
+        let val = ArrayStruct([1, 0xf2, 3, 0xf4]);
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "\"0x01f203f4\"");
+        assert_eq!(hr_deserialize::<ArrayStruct>(&val_str).unwrap(), val);
+
+        let val_bytes = bin_serialize(&val).unwrap();
+        assert_eq!(val_bytes, [0xc4, 4, 1, 0xf2, 3, 0xf4]);
+        assert_eq!(bin_deserialize::<ArrayStruct>(&val_bytes).unwrap(), val);
+    }
+
+    #[test]
+    fn legacy_human_readable() {
+        let legacy_str = hr_serialize(LegacyArray([1, 0xf2, 3, 0xf4])).unwrap();
+        assert_eq!(legacy_str, "[1,242,3,244]");
+
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>(&legacy_str).unwrap(),
+            ArrayStruct([1, 0xf2, 3, 0xf4])
+        );
+        assert_eq!(
+            hr_deserialize::<VectorStruct>(&legacy_str).unwrap(),
+            VectorStruct([1, 0xf2, 3, 0xf4].into())
+        );
+        assert_eq!(
+            hr_deserialize::<BorrowStruct>(&legacy_str).unwrap(),
+            BorrowStruct(Borrow([1, 0xf2, 3, 0xf4]))
+        );
+        #[cfg(feature = "generic-array-014")]
+        assert_eq!(
+            hr_deserialize::<GenericArray014Struct>(&legacy_str).unwrap(),
+            GenericArray014Struct([1, 0xf2, 3, 0xf4].into())
+        );
+
+        // Strict encodings still reject sequences
+        assert_eq!(
+            hr_deserialize::<StrictArrayStruct>(&legacy_str).unwrap_err(),
+            "invalid type: sequence, expected a bytestring of length 4 at line 1 column 0"
+        );
+    }
+
+    #[test]
+    fn legacy_binary() {
+        let legacy_bytes = bin_serialize(LegacyVector([1, 0xf2, 3, 0xf4].into())).unwrap();
+        assert_eq!(legacy_bytes, [0x94, 1, 0xcc, 0xf2, 3, 0xcc, 0xf4]);
+
+        assert_eq!(
+            bin_deserialize::<ArrayStruct>(&legacy_bytes).unwrap(),
+            ArrayStruct([1, 0xf2, 3, 0xf4])
+        );
+        assert_eq!(
+            bin_deserialize::<VectorStruct>(&legacy_bytes).unwrap(),
+            VectorStruct([1, 0xf2, 3, 0xf4].into())
+        );
+        assert_eq!(
+            bin_deserialize::<BorrowStruct>(&legacy_bytes).unwrap(),
+            BorrowStruct(Borrow([1, 0xf2, 3, 0xf4]))
+        );
+        #[cfg(feature = "generic-array-014")]
+        assert_eq!(
+            bin_deserialize::<GenericArray014Struct>(&legacy_bytes).unwrap(),
+            GenericArray014Struct([1, 0xf2, 3, 0xf4].into())
+        );
+
+        assert_eq!(
+            bin_deserialize::<StrictArrayStruct>(&legacy_bytes).unwrap_err(),
+            "invalid type: sequence, expected a bytestring of length 4"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>("[1,2,3]").unwrap_err(),
+            "Expected a bytestring of length 4, got 3 at line 1 column 7"
+        );
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>("[1,2,3,4,5]").unwrap_err(),
+            "Expected a bytestring of length 4, got 5 at line 1 column 11"
+        );
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>("[1,2,3,256]").unwrap_err(),
+            "invalid value: integer `256`, expected u8 at line 1 column 10"
+        );
+        assert_eq!(
+            hr_deserialize::<BorrowStruct>("[1,2,3]").unwrap_err(),
+            instantiation_message(
+                "serde_encoded_bytes::encoding::lenient::tests::Borrow",
+                "from a byte slice of length 3: Wrong length at line 1 column 7"
+            )
+        );
+        #[cfg(feature = "generic-array-014")]
+        assert_eq!(
+            hr_deserialize::<GenericArray014Struct>("[1,2,3,4,5]").unwrap_err(),
+            "Expected a bytestring of length 4, got 5 at line 1 column 11"
+        );
+    }
+}
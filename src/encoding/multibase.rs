@@ -0,0 +1,196 @@
+use core::{fmt, marker::PhantomData};
+
+use super::{DecodeError, EncodeError, Encoding};
+
+#[cfg(any(feature = "hex", test))]
+use super::{HexNoPrefix, HexNoPrefixUpper};
+
+#[cfg(feature = "base64")]
+use super::{Base64, Base64Padded, Base64Url, Base64UrlPadded};
+
+#[cfg(feature = "base32")]
+use super::{Base32, Base32Hex};
+
+#[cfg(feature = "base58")]
+use super::Base58;
+
+/// An encoding that has a code in the [multibase](https://github.com/multiformats/multibase) table.
+pub trait MultibaseCode: Encoding {
+    /// The prefix character identifying the encoding.
+    const CODE: char;
+}
+
+#[cfg(any(feature = "hex", test))]
+impl MultibaseCode for HexNoPrefix {
+    const CODE: char = 'f';
+}
+
+#[cfg(any(feature = "hex", test))]
+impl MultibaseCode for HexNoPrefixUpper {
+    const CODE: char = 'F';
+}
+
+#[cfg(feature = "base64")]
+impl MultibaseCode for Base64 {
+    const CODE: char = 'm';
+}
+
+#[cfg(feature = "base64")]
+impl MultibaseCode for Base64Padded {
+    const CODE: char = 'M';
+}
+
+#[cfg(feature = "base64")]
+impl MultibaseCode for Base64Url {
+    const CODE: char = 'u';
+}
+
+#[cfg(feature = "base64")]
+impl MultibaseCode for Base64UrlPadded {
+    const CODE: char = 'U';
+}
+
+#[cfg(feature = "base32")]
+impl MultibaseCode for Base32 {
+    const CODE: char = 'B';
+}
+
+#[cfg(feature = "base32")]
+impl MultibaseCode for Base32Hex {
+    const CODE: char = 'V';
+}
+
+#[cfg(feature = "base58")]
+impl MultibaseCode for Base58 {
+    const CODE: char = 'z';
+}
+
+/// A self-describing [multibase](https://github.com/multiformats/multibase) encoding.
+///
+/// On encoding, the byte sequence is encoded with `Enc` and prefixed by its code.
+/// On decoding, the encoding is selected by the prefix, regardless of `Enc`;
+/// the supported codes are the ones of the [`MultibaseCode`] implementors
+/// available with the enabled features:
+/// `f` and `F` (hex), `m` and `M` (base64), `u` and `U` (base64-url),
+/// `B` (base32), `V` (base32-hex), and `z` (base58).
+/// An empty string or an unsupported code results in [`DecodeError::UnknownEncoding`].
+pub struct Multibase<Enc>(PhantomData<Enc>);
+
+impl<Enc: MultibaseCode> Encoding for Multibase<Enc> {
+    fn check_encodable(bytes: &[u8]) -> Result<(), EncodeError> {
+        Enc::check_encodable(bytes)
+    }
+
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        writer.write_char(Enc::CODE)?;
+        Enc::encode_to(bytes, writer)
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        let mut chars = string.chars();
+        let Some(code) = chars.next() else {
+            return Err(DecodeError::UnknownEncoding);
+        };
+        let encoded = chars.as_str();
+        let result = match code {
+            #[cfg(any(feature = "hex", test))]
+            'f' => HexNoPrefix::decode_into(encoded, buffer),
+            #[cfg(any(feature = "hex", test))]
+            'F' => HexNoPrefixUpper::decode_into(encoded, buffer),
+            #[cfg(feature = "base64")]
+            'm' => Base64::decode_into(encoded, buffer),
+            #[cfg(feature = "base64")]
+            'M' => Base64Padded::decode_into(encoded, buffer),
+            #[cfg(feature = "base64")]
+            'u' => Base64Url::decode_into(encoded, buffer),
+            #[cfg(feature = "base64")]
+            'U' => Base64UrlPadded::decode_into(encoded, buffer),
+            #[cfg(feature = "base32")]
+            'B' => Base32::decode_into(encoded, buffer),
+            #[cfg(feature = "base32")]
+            'V' => Base32Hex::decode_into(encoded, buffer),
+            #[cfg(feature = "base58")]
+            'z' => Base58::decode_into(encoded, buffer),
+            _ => return Err(DecodeError::UnknownEncoding),
+        };
+        result.map_err(|err| err.shifted(code.len_utf8()))
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::Multibase;
+    use crate::{
+        tests::common::{hr_deserialize, hr_serialize},
+        ArrayLike, HexNoPrefix,
+    };
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStruct(#[serde(with = "ArrayLike::<Multibase<HexNoPrefix>>")] [u8; 4]);
+
+    #[test]
+    fn roundtrip() {
+        let val = ArrayStruct([1, 0xf2, 63, 0xf4]);
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "\"f01f23ff4\"");
+        let val_back = hr_deserialize::<ArrayStruct>(&val_str).unwrap();
+        assert_eq!(val, val_back);
+
+        assert_eq!(hr_deserialize::<ArrayStruct>("\"F01F23FF4\"").unwrap(), val);
+    }
+
+    #[cfg(all(feature = "base64", feature = "base32", feature = "base58"))]
+    #[test]
+    fn mixed_bases() {
+        use crate::Base58;
+
+        #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+        struct Base58Struct(#[serde(with = "ArrayLike::<Multibase<Base58>>")] [u8; 4]);
+
+        let val = Base58Struct([1, 0xf2, 63, 0xf4]);
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, "\"z3tMgw\"");
+
+        for string in [
+            "\"z3tMgw\"",
+            "\"f01f23ff4\"",
+            "\"mAfI/9A\"",
+            "\"MAfI/9A==\"",
+            "\"uAfI_9A\"",
+            "\"UAfI_9A==\"",
+            "\"BAHZD75A\"",
+            "\"V07P3VT0\"",
+        ] {
+            assert_eq!(hr_deserialize::<Base58Struct>(string).unwrap(), val);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>("\"x01f23ff4\"").unwrap_err(),
+            concat![
+                "invalid value: string \"x01f23ff4\", ",
+                "expected a string in a known encoding at line 1 column 11"
+            ]
+        );
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>("\"\"").unwrap_err(),
+            concat![
+                "invalid value: string \"\", ",
+                "expected a string in a known encoding at line 1 column 2"
+            ]
+        );
+        // The offset includes the base code
+        assert_eq!(
+            hr_deserialize::<ArrayStruct>("\"f01g23ff4\"").unwrap_err(),
+            concat![
+                "invalid value: string \"f01g23ff4\", ",
+                "expected a valid character at offset 3 at line 1 column 11"
+            ]
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use core::{cell::Cell, fmt};
+
+use super::{DecodeError, DynEncoding, EncodeError, Encoding, EncodingKind};
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static CURRENT: Cell<Option<EncodingKind>> = const { Cell::new(None) };
+}
+
+#[cfg(feature = "std")]
+fn replace_current(encoding: Option<EncodingKind>) -> Option<EncodingKind> {
+    CURRENT.with(|current| current.replace(encoding))
+}
+
+#[cfg(feature = "std")]
+fn get_current() -> Option<EncodingKind> {
+    CURRENT.with(Cell::get)
+}
+
+// Without `std` there are no thread-locals, so a global protected by a critical section is used.
+#[cfg(not(feature = "std"))]
+static CURRENT: critical_section::Mutex<Cell<Option<EncodingKind>>> =
+    critical_section::Mutex::new(Cell::new(None));
+
+#[cfg(not(feature = "std"))]
+fn replace_current(encoding: Option<EncodingKind>) -> Option<EncodingKind> {
+    critical_section::with(|cs| CURRENT.borrow(cs).replace(encoding))
+}
+
+#[cfg(not(feature = "std"))]
+fn get_current() -> Option<EncodingKind> {
+    critical_section::with(|cs| CURRENT.borrow(cs).get())
+}
+
+/// Restores the previous encoding when the scope ends.
+struct ScopeGuard(Option<EncodingKind>);
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        replace_current(self.0);
+    }
+}
+
+/// Calls `f` with `encoding` used by [`Scoped`], restoring the previous one afterwards.
+///
+/// With the `std` feature the setting is thread-local.
+/// Otherwise, it is global and guarded by a critical section
+/// (so an implementation of [`critical-section`](https://crates.io/crates/critical-section)
+/// must be provided by the final binary).
+pub fn with_encoding<R>(encoding: EncodingKind, f: impl FnOnce() -> R) -> R {
+    let _guard = ScopeGuard(replace_current(Some(encoding)));
+    f()
+}
+
+/// An encoding selected at serialization or deserialization time with [`with_encoding`].
+///
+/// (De)serializing outside of [`with_encoding`] results in an error
+/// ([`EncodeError::UnknownEncoding`] or [`DecodeError::UnknownEncoding`]),
+/// and [`Encoding::encode`] panics.
+///
+/// Note that sequences of integers are not accepted on deserialization
+/// (see [`Encoding::ACCEPTS_SEQUENCES`]) regardless of the selected encoding.
+pub struct Scoped;
+
+impl Encoding for Scoped {
+    fn check_encodable(bytes: &[u8]) -> Result<(), EncodeError> {
+        get_current()
+            .ok_or(EncodeError::UnknownEncoding)?
+            .dyn_check_encodable(bytes)
+    }
+
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result {
+        get_current()
+            .ok_or(fmt::Error)?
+            .dyn_encode_to(bytes, writer)
+    }
+
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError> {
+        get_current()
+            .ok_or(DecodeError::UnknownEncoding)?
+            .dyn_decode_into(string, buffer)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::{with_encoding, Scoped};
+    use crate::{
+        tests::common::{hr_deserialize, hr_serialize},
+        ArrayLike, EncodingKind,
+    };
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayStruct(#[serde(with = "ArrayLike::<Scoped>")] [u8; 4]);
+
+    /// Runs the test body while holding the critical section,
+    /// since without `std` the selected encoding is shared between the test threads.
+    /// (The `std` implementation of the critical section used in tests is reentrant.)
+    fn exclusive(f: impl FnOnce()) {
+        critical_section::with(|_| f());
+    }
+
+    #[test]
+    fn roundtrip() {
+        exclusive(|| {
+            let val = ArrayStruct([1, 0xf2, 63, 0xf4]);
+
+            let val_str = with_encoding(EncodingKind::Hex, || hr_serialize(&val)).unwrap();
+            assert_eq!(val_str, "\"0x01f23ff4\"");
+            let val_back = with_encoding(EncodingKind::Hex, || {
+                hr_deserialize::<ArrayStruct>(&val_str)
+            })
+            .unwrap();
+            assert_eq!(val_back, val);
+
+            let val_str =
+                with_encoding(EncodingKind::HexNoPrefixUpper, || hr_serialize(&val)).unwrap();
+            assert_eq!(val_str, "\"01F23FF4\"");
+        });
+    }
+
+    #[test]
+    fn nested() {
+        exclusive(|| {
+            let val = ArrayStruct([1, 0xf2, 63, 0xf4]);
+            with_encoding(EncodingKind::Hex, || {
+                let inner =
+                    with_encoding(EncodingKind::HexNoPrefix, || hr_serialize(&val)).unwrap();
+                assert_eq!(inner, "\"01f23ff4\"");
+                let outer = hr_serialize(&val).unwrap();
+                assert_eq!(outer, "\"0x01f23ff4\"");
+            });
+        });
+    }
+
+    #[test]
+    fn errors() {
+        exclusive(|| {
+            let expected =
+                "No encoding is selected for `Scoped`; use `with_encoding()` to select one";
+            assert_eq!(
+                hr_serialize(ArrayStruct([1, 0xf2, 63, 0xf4])).unwrap_err(),
+                expected
+            );
+            assert_eq!(
+                hr_deserialize::<ArrayStruct>("\"0x01f23ff4\"").unwrap_err(),
+                concat![
+                    "invalid value: string \"0x01f23ff4\", ",
+                    "expected a string in a known encoding at line 1 column 12"
+                ]
+            );
+            assert_eq!(
+                with_encoding(EncodingKind::Hex, || hr_deserialize::<ArrayStruct>(
+                    "\"01f23ff4\""
+                ))
+                .unwrap_err(),
+                concat![
+                    "invalid value: string \"01f23ff4\", ",
+                    "expected a string starting with `0x` at line 1 column 10"
+                ]
+            );
+        });
+    }
+}
@@ -1,12 +1,66 @@
-use alloc::{string::String, vec::Vec};
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt;
 
-use serde::de;
+use super::{DecodeError, EncodeError};
 
 /// A trait for encoding bytes into strings.
 pub trait Encoding {
+    /// Whether sequences of integers (`serde`'s default representation of byte arrays)
+    /// are accepted on deserialization in addition to the encoded strings and bytestrings.
+    ///
+    /// `false` by default; use the [`Lenient`](`crate::Lenient`) wrapper to enable it.
+    const ACCEPTS_SEQUENCES: bool = false;
+
+    /// Checks that the byte sequence can be encoded.
+    ///
+    /// Called before [`encode_to`](`Self::encode_to`) on serialization.
+    /// The default implementation accepts any byte sequence.
+    fn check_encodable(_bytes: &[u8]) -> Result<(), EncodeError> {
+        Ok(())
+    }
+
+    /// Encodes the byte sequence, writing the result into `writer`.
+    ///
+    /// This is the method used on serialization, and it should not allocate,
+    /// so that the crate could be used with serializers that do not have an allocator available.
+    fn encode_to<W: fmt::Write>(bytes: &[u8], writer: &mut W) -> fmt::Result;
+
+    /// Decodes the byte sequence into `buffer`, returning the length of the decoded sequence.
+    ///
+    /// If the returned length is greater than the length of `buffer`,
+    /// the contents of `buffer` are unspecified.
+    /// The returned length must not depend on the length of `buffer`,
+    /// and neither must the result of validation: invalid strings are rejected
+    /// even if `buffer` is too small to hold the decoded sequence.
+    ///
+    /// This is the method used on deserialization of fixed-size containers, and it should not allocate.
+    fn decode_into(string: &str, buffer: &mut [u8]) -> Result<usize, DecodeError>;
+
     /// Encodes the byte sequence.
-    fn encode(bytes: &[u8]) -> String;
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`encode_to`](`Self::encode_to`) fails,
+    /// which only happens if the byte sequence cannot be encoded
+    /// (see [`check_encodable`](`Self::check_encodable`)).
+    #[cfg(feature = "alloc")]
+    fn encode(bytes: &[u8]) -> String {
+        let mut result = String::new();
+        Self::encode_to(bytes, &mut result)
+            .expect("the byte sequence should satisfy `Encoding::check_encodable()`");
+        result
+    }
 
     /// Decodes the byte sequence.
-    fn decode<E: de::Error>(string: &str) -> Result<Vec<u8>, E>;
+    ///
+    /// The default implementation calls [`decode_into`](`Self::decode_into`) twice:
+    /// first to find out the length of the result, and then to fill the allocated buffer.
+    #[cfg(feature = "alloc")]
+    fn decode(string: &str) -> Result<Vec<u8>, DecodeError> {
+        let len = Self::decode_into(string, &mut [])?;
+        let mut bytes = vec![0u8; len];
+        Self::decode_into(string, &mut bytes)?;
+        Ok(bytes)
+    }
 }
@@ -12,25 +12,85 @@
     unused_qualifications
 )]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
+mod collections;
 mod containers;
+mod encoded;
 mod encoding;
 mod low_level;
+#[cfg(feature = "serde_with")]
+mod serde_as;
 
 #[cfg(test)]
 mod tests;
 
-pub use containers::{ArrayLike, BorrowedSliceLike, BoxedArrayLike, SliceLike};
-pub use encoding::Encoding;
+pub use collections::{MapKeys, MapKeysValues, SeqOf};
+pub use containers::{
+    ArrayLike, ArrayTupleLike, BorrowedSliceLike, BoxedArrayLike, Container, OptionArrayLike,
+    OptionArrayTupleLike, OptionBorrowedSliceLike, OptionBoxedArrayLike, OptionSliceLike,
+    SliceLike,
+};
+pub use encoded::Encoded;
+#[cfg(feature = "scoped")]
+pub use encoding::{with_encoding, Scoped};
+pub use encoding::{DecodeError, DynEncoding, EncodeError, Encoding, EncodingKind, Lenient};
+
+pub use low_level::{
+    deserialize_array_with_encoding, deserialize_slice_with_encoding, serialize_with_encoding,
+};
+
+#[cfg(feature = "alloc")]
+pub use containers::{BorrowedBytes, FromBorrowedBytes, OptionBorrowedBytes};
 
 #[cfg(feature = "generic-array-014")]
-pub use containers::GenericArray014;
+pub use containers::{GenericArray014, OptionGenericArray014};
+
+#[cfg(feature = "derive")]
+pub use serde_encoded_bytes_derive::EncodedBytes;
+
+/// Re-exports for the code generated by the derive macro.
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub mod __private {
+    pub use serde;
+}
 
 // Specifically enable `Hex` for tests, since we need some encoding to be specified.
 // Should be removed when https://github.com/rust-lang/cargo/issues/2911 is fixed.
 #[cfg(any(feature = "hex", test))]
-pub use encoding::Hex;
+pub use encoding::{Hex, HexNoPrefix, HexNoPrefixUpper, HexOptionalPrefix, HexUpper};
+
+#[cfg(any(
+    feature = "hex",
+    feature = "base64",
+    feature = "base32",
+    feature = "base58",
+    test
+))]
+pub use encoding::{Multibase, MultibaseCode};
 
 #[cfg(feature = "base64")]
-pub use encoding::{Base64, Base64Url};
+pub use encoding::{
+    Base64, Base64AnyPadding, Base64Mime, Base64Padded, Base64Url, Base64UrlAnyPadding,
+    Base64UrlPadded, Base64Wrapped,
+};
+
+#[cfg(feature = "base32")]
+pub use encoding::{Base32, Base32Hex};
+
+#[cfg(feature = "base58")]
+pub use encoding::{Base58, Base58Check};
+
+#[cfg(feature = "bech32")]
+pub use encoding::{Bech32, Bech32Hrp, Bech32m};
+
+#[cfg(feature = "base85")]
+pub use encoding::{Ascii85, Base85, Z85};
+
+#[cfg(feature = "eip55")]
+pub use encoding::Eip55;
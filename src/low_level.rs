@@ -1,27 +1,285 @@
-use alloc::format;
 use core::{any::type_name, fmt, marker::PhantomData};
 
-use serde::{de, Deserializer, Serializer};
+use serde::{de, ser, ser::SerializeTuple, Deserializer, Serialize, Serializer};
 
-use crate::encoding::Encoding;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::containers::FromBorrowedBytes;
+use crate::encoding::{DecodeError, DynEncoding, Encoding};
+
+/// A `de::Expected` adapter describing what a valid string would look like
+/// at the point where decoding failed.
+struct ExpectedBy(DecodeError);
+
+impl de::Expected for ExpectedBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            DecodeError::InvalidCharacter { offset, .. } => {
+                write!(f, "a valid character at offset {offset}")
+            }
+            DecodeError::InvalidLength { expected, .. } => f.write_str(expected),
+            DecodeError::InvalidPadding => f.write_str("valid padding"),
+            DecodeError::MissingPrefix { prefix } => write!(f, "a string starting with `{prefix}`"),
+            DecodeError::MissingSuffix { suffix } => write!(f, "a string ending with `{suffix}`"),
+            DecodeError::ChecksumMismatch => f.write_str("a valid checksum"),
+            DecodeError::UnknownEncoding => f.write_str("a string in a known encoding"),
+        }
+    }
+}
+
+/// Converts a decoding error of `string` into a deserializer error.
+fn decode_error<E: de::Error>(string: &str, err: DecodeError) -> E {
+    match err {
+        DecodeError::InvalidLength { length, .. } => {
+            de::Error::invalid_length(length, &ExpectedBy(err))
+        }
+        _ => de::Error::invalid_value(de::Unexpected::Str(string), &ExpectedBy(err)),
+    }
+}
+
+/// The encoding used for (de)serialization: either fixed by a type, or selected at runtime.
+trait EncodingSource: Copy {
+    fn accepts_sequences(self) -> bool;
+
+    fn check_encodable<E: ser::Error>(self, bytes: &[u8]) -> Result<(), E>;
+
+    fn encode_to<W: fmt::Write>(self, bytes: &[u8], writer: &mut W) -> fmt::Result;
+
+    fn decode_into<E: de::Error>(self, string: &str, buffer: &mut [u8]) -> Result<usize, E>;
+
+    #[cfg(feature = "alloc")]
+    fn decode<E: de::Error>(self, string: &str) -> Result<Vec<u8>, E>;
+}
+
+/// An encoding fixed by the type parameter.
+struct Static<Enc>(PhantomData<Enc>);
+
+impl<Enc> Static<Enc> {
+    const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<Enc> Clone for Static<Enc> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Enc> Copy for Static<Enc> {}
+
+impl<Enc: Encoding> EncodingSource for Static<Enc> {
+    fn accepts_sequences(self) -> bool {
+        Enc::ACCEPTS_SEQUENCES
+    }
+
+    fn check_encodable<E: ser::Error>(self, bytes: &[u8]) -> Result<(), E> {
+        Enc::check_encodable(bytes).map_err(ser::Error::custom)
+    }
+
+    fn encode_to<W: fmt::Write>(self, bytes: &[u8], writer: &mut W) -> fmt::Result {
+        Enc::encode_to(bytes, writer)
+    }
+
+    fn decode_into<E: de::Error>(self, string: &str, buffer: &mut [u8]) -> Result<usize, E> {
+        Enc::decode_into(string, buffer).map_err(|err| decode_error(string, err))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn decode<E: de::Error>(self, string: &str) -> Result<Vec<u8>, E> {
+        Enc::decode(string).map_err(|err| decode_error(string, err))
+    }
+}
+
+impl EncodingSource for &dyn DynEncoding {
+    fn accepts_sequences(self) -> bool {
+        self.dyn_accepts_sequences()
+    }
+
+    fn check_encodable<E: ser::Error>(self, bytes: &[u8]) -> Result<(), E> {
+        self.dyn_check_encodable(bytes).map_err(ser::Error::custom)
+    }
+
+    fn encode_to<W: fmt::Write>(self, bytes: &[u8], writer: &mut W) -> fmt::Result {
+        self.dyn_encode_to(bytes, writer)
+    }
+
+    fn decode_into<E: de::Error>(self, string: &str, buffer: &mut [u8]) -> Result<usize, E> {
+        self.dyn_decode_into(string, buffer)
+            .map_err(|err| decode_error(string, err))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn decode<E: de::Error>(self, string: &str) -> Result<Vec<u8>, E> {
+        self.dyn_decode(string)
+            .map_err(|err| decode_error(string, err))
+    }
+}
+
+/// A `Display` adapter encoding the bytes on the fly, so that no allocation is needed.
+struct EncodedStr<'a, Src>(&'a [u8], Src);
+
+impl<Src: EncodingSource> fmt::Display for EncodedStr<'_, Src> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.1.encode_to(self.0, f)
+    }
+}
+
+fn serialize_bytestring<Src, S>(src: Src, value: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    Src: EncodingSource,
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        src.check_encodable(value)?;
+        serializer.collect_str(&EncodedStr(value, src))
+    } else {
+        serializer.serialize_bytes(value)
+    }
+}
 
 pub(crate) fn serialize_slice<Enc, S>(value: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    Enc: Encoding,
+{
+    serialize_bytestring(Static::<Enc>::new(), value, serializer)
+}
+
+pub(crate) fn serialize_array_as_tuple<Enc, S>(
+    value: &[u8],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
     Enc: Encoding,
 {
     if serializer.is_human_readable() {
-        serializer.serialize_str(&Enc::encode(value))
+        serialize_slice::<Enc, _>(value, serializer)
     } else {
-        serializer.serialize_bytes(value)
+        let mut tuple = serializer.serialize_tuple(value.len())?;
+        for byte in value {
+            tuple.serialize_element(byte)?;
+        }
+        tuple.end()
     }
 }
 
-struct SliceVisitor<Enc, T, E>(PhantomData<(Enc, T, E)>);
+/// A `Serialize` adapter for the `Some` variant of an optional bytestring.
+struct Bytestring<'a, Enc>(&'a [u8], PhantomData<Enc>);
+
+impl<Enc: Encoding> Serialize for Bytestring<'_, Enc> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_slice::<Enc, _>(self.0, serializer)
+    }
+}
+
+/// A `Serialize` adapter for the `Some` variant of an optional array serialized as a tuple.
+struct BytesTuple<'a, Enc>(&'a [u8], PhantomData<Enc>);
+
+impl<Enc: Encoding> Serialize for BytesTuple<'_, Enc> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_array_as_tuple::<Enc, _>(self.0, serializer)
+    }
+}
+
+pub(crate) fn serialize_option_slice<Enc, S>(
+    value: Option<&[u8]>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    Enc: Encoding,
+{
+    match value {
+        Some(value) => serializer.serialize_some(&Bytestring::<Enc>(value, PhantomData)),
+        None => serializer.serialize_none(),
+    }
+}
 
-impl<Enc, T, E> de::Visitor<'_> for SliceVisitor<Enc, T, E>
+pub(crate) fn serialize_option_array_as_tuple<Enc, S>(
+    value: Option<&[u8]>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
 where
+    S: Serializer,
     Enc: Encoding,
+{
+    match value {
+        Some(value) => serializer.serialize_some(&BytesTuple::<Enc>(value, PhantomData)),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Reports a bytestring of a wrong length.
+fn length_error<E: de::Error>(expected_len: usize, len: usize) -> E {
+    de::Error::custom(format_args!(
+        "Expected a bytestring of length {expected_len}, got {len}"
+    ))
+}
+
+/// Reports a failure to convert the decoded bytes (`len` of them) into `T`.
+///
+/// The name of `T` is omitted with the `hide-type-names` feature.
+fn instantiation_error<T: ?Sized, E: de::Error>(len: usize, err: impl fmt::Display) -> E {
+    if cfg!(feature = "hide-type-names") {
+        de::Error::custom(format_args!(
+            "Failed to instantiate the target type from a byte slice of length {len}: {err}"
+        ))
+    } else {
+        de::Error::custom(format_args!(
+            "Failed to instantiate `{}` from a byte slice of length {len}: {err}",
+            type_name::<T>()
+        ))
+    }
+}
+
+/// Fills `buffer` from `seq`, which must contain exactly `buffer.len()` bytes.
+fn fill_from_seq<'de, A>(mut seq: A, buffer: &mut [u8]) -> Result<(), A::Error>
+where
+    A: de::SeqAccess<'de>,
+{
+    let expected_len = buffer.len();
+    for (i, byte) in buffer.iter_mut().enumerate() {
+        *byte = seq
+            .next_element()?
+            .ok_or_else(|| length_error(expected_len, i))?;
+    }
+
+    // Formats that are not self-describing will only provide the requested number of elements,
+    // but the others may have more.
+    let mut extra_len = 0;
+    while seq.next_element::<de::IgnoredAny>()?.is_some() {
+        extra_len += 1;
+    }
+    if extra_len != 0 {
+        return Err(length_error(expected_len, expected_len + extra_len));
+    }
+
+    Ok(())
+}
+
+/// Collects the bytes from `seq` into a vector.
+#[cfg(feature = "alloc")]
+fn collect_seq<'de, A>(mut seq: A) -> Result<Vec<u8>, A::Error>
+where
+    A: de::SeqAccess<'de>,
+{
+    // The size hint comes from the input, so it cannot be fully trusted.
+    let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+    while let Some(byte) = seq.next_element()? {
+        bytes.push(byte);
+    }
+    Ok(bytes)
+}
+
+struct SliceVisitor<Src, T, E>(Src, PhantomData<(T, E)>);
+
+impl<'de, Src, T, E> de::Visitor<'de> for SliceVisitor<Src, T, E>
+where
+    Src: EncodingSource,
     T: for<'a> TryFrom<&'a [u8], Error = E>,
     E: fmt::Display,
 {
@@ -31,18 +289,16 @@ where
         write!(f, "a bytestring")
     }
 
+    #[cfg(feature = "alloc")]
     fn visit_str<SE>(self, v: &str) -> Result<Self::Value, SE>
     where
         SE: de::Error,
     {
-        let bytes = Enc::decode(v)?;
+        let bytes = self.0.decode(v)?;
         let bytes_len = bytes.len();
-        AsRef::<[u8]>::as_ref(&bytes).try_into().map_err(|err| {
-            de::Error::custom(format!(
-                "Failed to instantiate `{}` from a byte slice of length {bytes_len}: {err}",
-                type_name::<T>()
-            ))
-        })
+        AsRef::<[u8]>::as_ref(&bytes)
+            .try_into()
+            .map_err(|err| instantiation_error::<T, _>(bytes_len, err))
     }
 
     fn visit_bytes<SE>(self, v: &[u8]) -> Result<Self::Value, SE>
@@ -50,20 +306,28 @@ where
         SE: de::Error,
     {
         let v_len = v.len();
-        v.try_into().map_err(|err| {
-            de::Error::custom(format!(
-                "Failed to instantiate `{}` from a byte slice of length {v_len}: {err}",
-                type_name::<T>()
-            ))
-        })
+        v.try_into()
+            .map_err(|err| instantiation_error::<T, _>(v_len, err))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        if !self.0.accepts_sequences() {
+            return Err(de::Error::invalid_type(de::Unexpected::Seq, &self));
+        }
+        let bytes = collect_seq(seq)?;
+        self.visit_bytes(&bytes)
     }
 }
 
-struct BorrowedSliceVisitor<Enc, T, E>(PhantomData<(Enc, T, E)>);
+struct BorrowedSliceVisitor<Src, T, E>(Src, PhantomData<(T, E)>);
 
-impl<Enc, T, E> de::Visitor<'_> for BorrowedSliceVisitor<Enc, T, E>
+impl<'de, Src, T, E> de::Visitor<'de> for BorrowedSliceVisitor<Src, T, E>
 where
-    Enc: Encoding,
+    Src: EncodingSource,
     T: Clone,
     for<'a> &'a T: TryFrom<&'a [u8], Error = E>,
     E: fmt::Display,
@@ -74,18 +338,16 @@ where
         write!(f, "a bytestring")
     }
 
+    #[cfg(feature = "alloc")]
     fn visit_str<SE>(self, v: &str) -> Result<Self::Value, SE>
     where
         SE: de::Error,
     {
-        let bytes = Enc::decode(v)?;
+        let bytes = self.0.decode(v)?;
         let bytes_len = bytes.len();
-        let result_ref: &T = AsRef::<[u8]>::as_ref(&bytes).try_into().map_err(|err| {
-            de::Error::custom(format!(
-                "Failed to instantiate `{}` from a byte slice of length {bytes_len}: {err}",
-                type_name::<T>()
-            ))
-        })?;
+        let result_ref: &T = AsRef::<[u8]>::as_ref(&bytes)
+            .try_into()
+            .map_err(|err| instantiation_error::<T, _>(bytes_len, err))?;
         Ok(result_ref.clone())
     }
 
@@ -94,21 +356,86 @@ where
         SE: de::Error,
     {
         let v_len = v.len();
-        let result_ref: &T = v.try_into().map_err(|err| {
-            de::Error::custom(format!(
-                "Failed to instantiate `{}` from a byte slice of length {v_len}: {err}",
-                type_name::<T>()
-            ))
-        })?;
+        let result_ref: &T = v
+            .try_into()
+            .map_err(|err| instantiation_error::<T, _>(v_len, err))?;
         Ok(result_ref.clone())
     }
+
+    #[cfg(feature = "alloc")]
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        if !self.0.accepts_sequences() {
+            return Err(de::Error::invalid_type(de::Unexpected::Seq, &self));
+        }
+        let bytes = collect_seq(seq)?;
+        self.visit_bytes(&bytes)
+    }
 }
 
-struct ArrayVisitor<Enc, T, E, const N: usize>(PhantomData<(Enc, T, E)>);
+#[cfg(feature = "alloc")]
+struct BorrowedBytesVisitor<Src, T>(Src, PhantomData<T>);
 
-impl<Enc, T, E, const N: usize> de::Visitor<'_> for ArrayVisitor<Enc, T, E, N>
+#[cfg(feature = "alloc")]
+impl<'de, Src, T> de::Visitor<'de> for BorrowedBytesVisitor<Src, T>
 where
-    Enc: Encoding,
+    Src: EncodingSource,
+    T: FromBorrowedBytes<'de>,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a bytestring")
+    }
+
+    fn visit_str<SE>(self, v: &str) -> Result<Self::Value, SE>
+    where
+        SE: de::Error,
+    {
+        let bytes = self.0.decode(v)?;
+        self.visit_byte_buf(bytes)
+    }
+
+    fn visit_borrowed_bytes<SE>(self, v: &'de [u8]) -> Result<Self::Value, SE>
+    where
+        SE: de::Error,
+    {
+        T::from_borrowed_bytes(v).map_err(|err| instantiation_error::<T, _>(v.len(), err))
+    }
+
+    fn visit_bytes<SE>(self, v: &[u8]) -> Result<Self::Value, SE>
+    where
+        SE: de::Error,
+    {
+        self.visit_byte_buf(v.into())
+    }
+
+    fn visit_byte_buf<SE>(self, v: Vec<u8>) -> Result<Self::Value, SE>
+    where
+        SE: de::Error,
+    {
+        let v_len = v.len();
+        T::from_owned_bytes(v).map_err(|err| instantiation_error::<T, _>(v_len, err))
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        if !self.0.accepts_sequences() {
+            return Err(de::Error::invalid_type(de::Unexpected::Seq, &self));
+        }
+        self.visit_byte_buf(collect_seq(seq)?)
+    }
+}
+
+struct ArrayVisitor<Src, T, E, const N: usize>(Src, PhantomData<(T, E)>);
+
+impl<'de, Src, T, E, const N: usize> de::Visitor<'de> for ArrayVisitor<Src, T, E, N>
+where
+    Src: EncodingSource,
     T: TryFrom<[u8; N], Error = E>,
     E: fmt::Display,
 {
@@ -122,19 +449,12 @@ where
     where
         SE: de::Error,
     {
-        let bytes = Enc::decode(v)?;
-        let bytes_len = bytes.len();
-        let arr = <[u8; N]>::try_from(bytes).map_err(|_| {
-            de::Error::custom(format!(
-                "Expected a bytestring of length {N}, got {bytes_len}",
-            ))
-        })?;
-        T::try_from(arr).map_err(|err| {
-            de::Error::custom(format!(
-                "Failed to instantiate `{}` from `[u8; {N}]`: {err}",
-                type_name::<T>()
-            ))
-        })
+        let mut arr = [0u8; N];
+        let bytes_len = self.0.decode_into(v, &mut arr)?;
+        if bytes_len != N {
+            return Err(length_error(N, bytes_len));
+        }
+        T::try_from(arr).map_err(|err| instantiation_error::<T, _>(N, err))
     }
 
     fn visit_bytes<SE>(self, v: &[u8]) -> Result<Self::Value, SE>
@@ -142,25 +462,53 @@ where
         SE: de::Error,
     {
         let v_len = v.len();
-        let arr = <[u8; N]>::try_from(v).map_err(|_| {
-            de::Error::custom(format!("Expected a bytestring of length {N}, got {v_len}",))
-        })?;
-        T::try_from(arr).map_err(|err| {
-            de::Error::custom(format!(
-                "Failed to instantiate `{}` from `[u8; {N}]`: {err}",
-                type_name::<T>()
-            ))
-        })
+        let arr = <[u8; N]>::try_from(v).map_err(|_| length_error(N, v_len))?;
+        T::try_from(arr).map_err(|err| instantiation_error::<T, _>(N, err))
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        if !self.0.accepts_sequences() {
+            return Err(de::Error::invalid_type(de::Unexpected::Seq, &self));
+        }
+        let mut arr = [0u8; N];
+        fill_from_seq(seq, &mut arr)?;
+        self.visit_bytes(&arr)
+    }
+}
+
+struct ArrayTupleVisitor<T, E, const N: usize>(PhantomData<(T, E)>);
+
+impl<'de, T, E, const N: usize> de::Visitor<'de> for ArrayTupleVisitor<T, E, N>
+where
+    T: TryFrom<[u8; N], Error = E>,
+    E: fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a tuple of {N} bytes")
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut arr = [0u8; N];
+        fill_from_seq(seq, &mut arr)?;
+        T::try_from(arr).map_err(|err| instantiation_error::<T, _>(N, err))
     }
 }
 
 #[cfg(feature = "generic-array-014")]
-struct GenericArray014Visitor<Enc, L>(PhantomData<(Enc, L)>);
+struct GenericArray014Visitor<Src, L>(Src, PhantomData<L>);
 
 #[cfg(feature = "generic-array-014")]
-impl<Enc, L> de::Visitor<'_> for GenericArray014Visitor<Enc, L>
+impl<'de, Src, L> de::Visitor<'de> for GenericArray014Visitor<Src, L>
 where
-    Enc: Encoding,
+    Src: EncodingSource,
     L: generic_array_014::ArrayLength<u8>,
 {
     type Value = generic_array_014::GenericArray<u8, L>;
@@ -173,14 +521,12 @@ where
     where
         SE: de::Error,
     {
-        let bytes = Enc::decode(v)?;
-        let bytes_len = bytes.len();
-        Self::Value::from_exact_iter(bytes).ok_or_else(|| {
-            de::Error::custom(format!(
-                "Expected a bytestring of length {}, got {bytes_len}",
-                L::to_usize()
-            ))
-        })
+        let mut arr = Self::Value::default();
+        let bytes_len = self.0.decode_into(v, &mut arr)?;
+        if bytes_len != L::to_usize() {
+            return Err(length_error(L::to_usize(), bytes_len));
+        }
+        Ok(arr)
     }
 
     fn visit_bytes<SE>(self, v: &[u8]) -> Result<Self::Value, SE>
@@ -188,28 +534,130 @@ where
         SE: de::Error,
     {
         let v_len = v.len();
-        Self::Value::from_exact_iter(v.iter().copied()).ok_or_else(|| {
-            de::Error::custom(format!(
-                "Expected a bytestring of length {}, got {v_len}",
-                L::to_usize()
-            ))
-        })
+        Self::Value::from_exact_iter(v.iter().copied())
+            .ok_or_else(|| length_error(L::to_usize(), v_len))
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        if !self.0.accepts_sequences() {
+            return Err(de::Error::invalid_type(de::Unexpected::Seq, &self));
+        }
+        let mut arr = Self::Value::default();
+        fill_from_seq(seq, &mut arr)?;
+        Ok(arr)
     }
 }
 
-pub(crate) fn deserialize_slice<'de, Enc: Encoding, T, E, D>(deserializer: D) -> Result<T, D::Error>
+fn deserialize_bytestring<'de, Src, D, V>(
+    src: Src,
+    deserializer: D,
+    visitor: V,
+) -> Result<V::Value, D::Error>
 where
+    Src: EncodingSource,
     D: Deserializer<'de>,
-    T: for<'a> TryFrom<&'a [u8], Error = E>,
-    E: fmt::Display,
+    V: de::Visitor<'de>,
 {
     if deserializer.is_human_readable() {
-        deserializer.deserialize_str(SliceVisitor::<Enc, T, E>(PhantomData))
+        if src.accepts_sequences() {
+            // Human-readable formats will only call `visit_seq()` if asked
+            // to deserialize an arbitrary value.
+            deserializer.deserialize_any(visitor)
+        } else {
+            deserializer.deserialize_str(visitor)
+        }
     } else {
-        deserializer.deserialize_bytes(SliceVisitor::<Enc, T, E>(PhantomData))
+        deserializer.deserialize_bytes(visitor)
+    }
+}
+
+/// A `DeserializeSeed` deserializing a bytestring with the given visitor.
+struct BytestringSeed<Src, V>(Src, V);
+
+impl<'de, Src, V> de::DeserializeSeed<'de> for BytestringSeed<Src, V>
+where
+    Src: EncodingSource,
+    V: de::Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserialize_bytestring(self.0, deserializer, self.1)
+    }
+}
+
+/// A `DeserializeSeed` deserializing an array serialized as a tuple.
+struct ArrayTupleSeed<Enc, T, E, const N: usize>(PhantomData<(Enc, T, E)>);
+
+impl<'de, Enc, T, E, const N: usize> de::DeserializeSeed<'de> for ArrayTupleSeed<Enc, T, E, N>
+where
+    Enc: Encoding,
+    T: TryFrom<[u8; N], Error = E>,
+    E: fmt::Display,
+{
+    type Value = T;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserialize_array_from_tuple::<Enc, N, _, _, _>(deserializer)
+    }
+}
+
+/// Deserializes an optional value, using `seed` for the `Some` variant.
+struct OptionVisitor<S>(S);
+
+impl<'de, S> de::Visitor<'de> for OptionVisitor<S>
+where
+    S: de::DeserializeSeed<'de>,
+{
+    type Value = Option<S::Value>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "an optional bytestring")
+    }
+
+    fn visit_none<SE>(self) -> Result<Self::Value, SE>
+    where
+        SE: de::Error,
+    {
+        Ok(None)
+    }
+
+    fn visit_unit<SE>(self) -> Result<Self::Value, SE>
+    where
+        SE: de::Error,
+    {
+        Ok(None)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.0.deserialize(deserializer).map(Some)
     }
 }
 
+fn deserialize_option<'de, S, D>(deserializer: D, seed: S) -> Result<Option<S::Value>, D::Error>
+where
+    S: de::DeserializeSeed<'de>,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_option(OptionVisitor(seed))
+}
+
+pub(crate) fn deserialize_slice<'de, Enc: Encoding, T, E, D>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: for<'a> TryFrom<&'a [u8], Error = E>,
+    E: fmt::Display,
+{
+    let src = Static::<Enc>::new();
+    deserialize_bytestring(src, deserializer, SliceVisitor::<_, T, E>(src, PhantomData))
+}
+
 pub(crate) fn deserialize_borrowed_slice<'de, Enc: Encoding, T, E, D>(
     deserializer: D,
 ) -> Result<T, D::Error>
@@ -219,25 +667,63 @@ where
     for<'a> &'a T: TryFrom<&'a [u8], Error = E>,
     E: fmt::Display,
 {
-    if deserializer.is_human_readable() {
-        deserializer.deserialize_str(BorrowedSliceVisitor::<Enc, T, E>(PhantomData))
-    } else {
-        deserializer.deserialize_bytes(BorrowedSliceVisitor::<Enc, T, E>(PhantomData))
-    }
+    let src = Static::<Enc>::new();
+    deserialize_bytestring(
+        src,
+        deserializer,
+        BorrowedSliceVisitor::<_, T, E>(src, PhantomData),
+    )
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn deserialize_borrowed_bytes<'de, Enc: Encoding, T, D>(
+    deserializer: D,
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromBorrowedBytes<'de>,
+{
+    let src = Static::<Enc>::new();
+    deserialize_bytestring(
+        src,
+        deserializer,
+        BorrowedBytesVisitor::<_, T>(src, PhantomData),
+    )
 }
 
 pub(crate) fn deserialize_array<'de, Enc: Encoding, const N: usize, T, E, D>(
     deserializer: D,
 ) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<[u8; N], Error = E>,
+    E: fmt::Display,
+{
+    let src = Static::<Enc>::new();
+    deserialize_bytestring(
+        src,
+        deserializer,
+        ArrayVisitor::<_, T, E, N>(src, PhantomData),
+    )
+}
+
+pub(crate) fn deserialize_array_from_tuple<'de, Enc: Encoding, const N: usize, T, E, D>(
+    deserializer: D,
+) -> Result<T, D::Error>
 where
     D: Deserializer<'de>,
     T: TryFrom<[u8; N], Error = E>,
     E: fmt::Display,
 {
     if deserializer.is_human_readable() {
-        deserializer.deserialize_str(ArrayVisitor::<Enc, T, E, N>(PhantomData))
+        let src = Static::<Enc>::new();
+        deserialize_bytestring(
+            src,
+            deserializer,
+            ArrayVisitor::<_, T, E, N>(src, PhantomData),
+        )
     } else {
-        deserializer.deserialize_bytes(ArrayVisitor::<Enc, T, E, N>(PhantomData))
+        deserializer.deserialize_tuple(N, ArrayTupleVisitor::<T, E, N>(PhantomData))
     }
 }
 
@@ -249,16 +735,147 @@ where
     D: Deserializer<'de>,
     L: generic_array_014::ArrayLength<u8>,
 {
-    if deserializer.is_human_readable() {
-        deserializer.deserialize_str(GenericArray014Visitor::<Enc, L>(PhantomData))
-    } else {
-        deserializer.deserialize_bytes(GenericArray014Visitor::<Enc, L>(PhantomData))
-    }
+    let src = Static::<Enc>::new();
+    deserialize_bytestring(
+        src,
+        deserializer,
+        GenericArray014Visitor::<_, L>(src, PhantomData),
+    )
+}
+
+pub(crate) fn deserialize_option_slice<'de, Enc: Encoding, T, E, D>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: for<'a> TryFrom<&'a [u8], Error = E>,
+    E: fmt::Display,
+{
+    let src = Static::<Enc>::new();
+    let visitor = SliceVisitor::<_, T, E>(src, PhantomData);
+    deserialize_option(deserializer, BytestringSeed(src, visitor))
+}
+
+pub(crate) fn deserialize_option_borrowed_slice<'de, Enc: Encoding, T, E, D>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Clone,
+    for<'a> &'a T: TryFrom<&'a [u8], Error = E>,
+    E: fmt::Display,
+{
+    let src = Static::<Enc>::new();
+    let visitor = BorrowedSliceVisitor::<_, T, E>(src, PhantomData);
+    deserialize_option(deserializer, BytestringSeed(src, visitor))
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn deserialize_option_borrowed_bytes<'de, Enc: Encoding, T, D>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromBorrowedBytes<'de>,
+{
+    let src = Static::<Enc>::new();
+    let visitor = BorrowedBytesVisitor::<_, T>(src, PhantomData);
+    deserialize_option(deserializer, BytestringSeed(src, visitor))
+}
+
+pub(crate) fn deserialize_option_array<'de, Enc: Encoding, const N: usize, T, E, D>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<[u8; N], Error = E>,
+    E: fmt::Display,
+{
+    let src = Static::<Enc>::new();
+    let visitor = ArrayVisitor::<_, T, E, N>(src, PhantomData);
+    deserialize_option(deserializer, BytestringSeed(src, visitor))
+}
+
+pub(crate) fn deserialize_option_array_from_tuple<'de, Enc: Encoding, const N: usize, T, E, D>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<[u8; N], Error = E>,
+    E: fmt::Display,
+{
+    deserialize_option(deserializer, ArrayTupleSeed::<Enc, T, E, N>(PhantomData))
+}
+
+#[cfg(feature = "generic-array-014")]
+pub(crate) fn deserialize_option_generic_array_014<'de, Enc: Encoding, L, D>(
+    deserializer: D,
+) -> Result<Option<generic_array_014::GenericArray<u8, L>>, D::Error>
+where
+    D: Deserializer<'de>,
+    L: generic_array_014::ArrayLength<u8>,
+{
+    let src = Static::<Enc>::new();
+    let visitor = GenericArray014Visitor::<_, L>(src, PhantomData);
+    deserialize_option(deserializer, BytestringSeed(src, visitor))
+}
+
+/// Serializes a bytestring with the encoding selected at runtime.
+///
+/// Same as [`SliceLike::serialize()`](`crate::SliceLike::serialize`),
+/// but takes the encoding as a value instead of a type parameter.
+pub fn serialize_with_encoding<S: Serializer>(
+    encoding: &dyn DynEncoding,
+    value: &[u8],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serialize_bytestring(encoding, value, serializer)
 }
 
-#[cfg(test)]
+/// Deserializes slice-like data with the encoding selected at runtime.
+///
+/// Same as [`SliceLike::deserialize()`](`crate::SliceLike::deserialize`),
+/// but takes the encoding as a value instead of a type parameter.
+pub fn deserialize_slice_with_encoding<'de, T, E, D>(
+    encoding: &dyn DynEncoding,
+    deserializer: D,
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: for<'a> TryFrom<&'a [u8], Error = E>,
+    E: fmt::Display,
+{
+    deserialize_bytestring(
+        encoding,
+        deserializer,
+        SliceVisitor::<_, T, E>(encoding, PhantomData),
+    )
+}
+
+/// Deserializes array-like data with the encoding selected at runtime.
+///
+/// Same as [`ArrayLike::deserialize()`](`crate::ArrayLike::deserialize`),
+/// but takes the encoding as a value instead of a type parameter.
+pub fn deserialize_array_with_encoding<'de, T, E, D, const N: usize>(
+    encoding: &dyn DynEncoding,
+    deserializer: D,
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<[u8; N], Error = E>,
+    E: fmt::Display,
+{
+    deserialize_bytestring(
+        encoding,
+        deserializer,
+        ArrayVisitor::<_, T, E, N>(encoding, PhantomData),
+    )
+}
+
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use alloc::{
+        borrow::Cow,
         format,
         string::{String, ToString},
         vec::Vec,
@@ -266,7 +883,13 @@ mod tests {
 
     use serde::{Deserialize, Serialize};
 
-    use crate::{encoding::Hex, ArrayLike, BorrowedSliceLike, SliceLike};
+    use crate::{
+        encoding::Hex,
+        tests::common::{
+            bin_deserialize, bin_serialize, hr_deserialize, hr_serialize, instantiation_message,
+        },
+        ArrayLike, ArrayTupleLike, BorrowedBytes, BorrowedSliceLike, SliceLike,
+    };
 
     #[cfg(feature = "generic-array-014")]
     use crate::GenericArray014;
@@ -286,6 +909,21 @@ mod tests {
         #[serde(with = "GenericArray014::<Hex>")] generic_array_014::GenericArray<u8, L>,
     );
 
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct BorrowedBytesStruct<'a>(#[serde(with = "BorrowedBytes::<Hex>")] &'a [u8]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct CowStruct<'a>(#[serde(borrow, with = "BorrowedBytes::<Hex>")] Cow<'a, [u8]>);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ArrayTupleStruct(#[serde(with = "ArrayTupleLike::<Hex>")] [u8; 4]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct WrongLengthTuple<const N: usize>(#[serde(with = "ArrayTupleLike::<Hex>")] [u8; N]);
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct BadArrayTupleStruct(#[serde(with = "ArrayTupleLike::<Hex>")] BadType);
+
     #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
     struct WrongLength(#[serde(with = "ArrayLike::<Hex>")] [u8; 5]);
 
@@ -358,22 +996,6 @@ mod tests {
     #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
     struct BadBorrowSliceStruct(#[serde(with = "BorrowedSliceLike::<Hex>")] BadType);
 
-    fn bin_serialize<T: Serialize>(value: T) -> Result<Vec<u8>, String> {
-        rmp_serde::encode::to_vec(&value).map_err(|err| err.to_string())
-    }
-
-    fn bin_deserialize<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> Result<T, String> {
-        rmp_serde::decode::from_slice(bytes).map_err(|err| err.to_string())
-    }
-
-    fn hr_serialize<T: Serialize>(value: T) -> Result<String, String> {
-        serde_json::to_string(&value).map_err(|err| err.to_string())
-    }
-
-    fn hr_deserialize<'de, T: Deserialize<'de>>(string: &'de str) -> Result<T, String> {
-        serde_json::from_str::<T>(string).map_err(|err| err.to_string())
-    }
-
     #[test]
     fn array_visitor_human_readable() {
         let val = ArrayStruct([1, 2, 3, 4]);
@@ -386,7 +1008,7 @@ mod tests {
         // Failed to decode
         assert_eq!(
             hr_deserialize::<ArrayStruct>("\"0x0102030\"").unwrap_err(),
-            "Odd number of digits at line 1 column 11"
+            "invalid length 7, expected an even number of hex digits at line 1 column 11"
         );
 
         // Wrong length
@@ -405,10 +1027,10 @@ mod tests {
         let bad_struct_str = hr_serialize(BadArrayStruct(BadType([1, 2, 3, 4]))).unwrap();
         assert_eq!(
             hr_deserialize::<BadArrayStruct>(&bad_struct_str).unwrap_err(),
-            concat![
-                "Failed to instantiate `serde_encoded_bytes::low_level::tests::BadType` ",
-                "from `[u8; 4]`: BadType cannot deserialize from `[u8; 4]` at line 1 column 12"
-            ]
+            instantiation_message(
+                "serde_encoded_bytes::low_level::tests::BadType",
+                "from a byte slice of length 4: BadType cannot deserialize from `[u8; 4]` at line 1 column 12"
+            )
         );
     }
 
@@ -439,10 +1061,62 @@ mod tests {
         let bad_struct_bytes = bin_serialize(BadArrayStruct(BadType([1, 2, 3, 4]))).unwrap();
         assert_eq!(
             bin_deserialize::<BadArrayStruct>(&bad_struct_bytes).unwrap_err(),
-            concat![
-                "Failed to instantiate `serde_encoded_bytes::low_level::tests::BadType` ",
-                "from `[u8; 4]`: BadType cannot deserialize from `[u8; 4]`"
-            ]
+            instantiation_message(
+                "serde_encoded_bytes::low_level::tests::BadType",
+                "from a byte slice of length 4: BadType cannot deserialize from `[u8; 4]`"
+            )
+        );
+    }
+
+    #[test]
+    fn array_tuple_visitor_human_readable() {
+        let val = ArrayTupleStruct([1, 2, 3, 4]);
+
+        // Same as `ArrayLike`
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(val_str, hr_serialize(ArrayStruct([1, 2, 3, 4])).unwrap());
+        let val_back = hr_deserialize::<ArrayTupleStruct>(&val_str).unwrap();
+        assert_eq!(val, val_back);
+    }
+
+    #[test]
+    fn array_tuple_visitor_binary() {
+        let val = ArrayTupleStruct([1, 2, 3, 4]);
+
+        // Normal operation
+        let val_bytes = bin_serialize(&val).unwrap();
+        // A MessagePack array of 4 integers
+        assert_eq!(val_bytes, [0x94, 1, 2, 3, 4]);
+        let val_back = bin_deserialize::<ArrayTupleStruct>(&val_bytes).unwrap();
+        assert_eq!(val, val_back);
+
+        // Wrong length
+        let longer_bytes = bin_serialize(WrongLengthTuple([1, 2, 3, 4, 5])).unwrap();
+        assert_eq!(
+            bin_deserialize::<ArrayTupleStruct>(&longer_bytes).unwrap_err(),
+            "Expected a bytestring of length 4, got 5"
+        );
+        let shorter_bytes = bin_serialize(WrongLengthTuple([1, 2, 3])).unwrap();
+        assert_eq!(
+            bin_deserialize::<ArrayTupleStruct>(&shorter_bytes).unwrap_err(),
+            "Expected a bytestring of length 4, got 3"
+        );
+
+        // Unexpected value type
+        let wrong_val_bytes = bin_serialize(WrongValue(0x01020304)).unwrap();
+        assert_eq!(
+            bin_deserialize::<ArrayTupleStruct>(&wrong_val_bytes).unwrap_err(),
+            "invalid type: integer `16909060`, expected a tuple of 4 bytes"
+        );
+
+        // A struct that always fails on deserialization
+        let bad_struct_bytes = bin_serialize(BadArrayTupleStruct(BadType([1, 2, 3, 4]))).unwrap();
+        assert_eq!(
+            bin_deserialize::<BadArrayTupleStruct>(&bad_struct_bytes).unwrap_err(),
+            instantiation_message(
+                "serde_encoded_bytes::low_level::tests::BadType",
+                "from a byte slice of length 4: BadType cannot deserialize from `[u8; 4]`"
+            )
         );
     }
 
@@ -458,7 +1132,7 @@ mod tests {
         // Failed to decode
         assert_eq!(
             hr_deserialize::<VectorStruct>("\"0x0102030\"").unwrap_err(),
-            "Odd number of digits at line 1 column 11"
+            "invalid length 7, expected an even number of hex digits at line 1 column 11"
         );
 
         // Unexpected value type
@@ -471,11 +1145,88 @@ mod tests {
         let bad_struct_str = hr_serialize(BadSliceStruct(BadType([1, 2, 3, 4]))).unwrap();
         assert_eq!(
             hr_deserialize::<BadSliceStruct>(&bad_struct_str).unwrap_err(),
-            concat![
-                "Failed to instantiate `serde_encoded_bytes::low_level::tests::BadType` ",
-                "from a byte slice of length 4: ",
-                "BadType cannot deserialize from `&[u8]` of length 4 at line 1 column 12"
-            ]
+            instantiation_message(
+                "serde_encoded_bytes::low_level::tests::BadType",
+                concat![
+                    "from a byte slice of length 4: ",
+                    "BadType cannot deserialize from `&[u8]` of length 4 at line 1 column 12"
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn borrowed_bytes_visitor_human_readable() {
+        // Normal operation; the bytes have to be decoded, so they are owned
+        let val = CowStruct(Cow::Borrowed(&[1, 2, 3, 4]));
+        let val_str = hr_serialize(&val).unwrap();
+        let val_back = hr_deserialize::<CowStruct<'_>>(&val_str).unwrap();
+        assert!(matches!(val_back.0, Cow::Owned(_)));
+        assert_eq!(val, val_back);
+
+        // Failed to decode
+        assert_eq!(
+            hr_deserialize::<CowStruct<'_>>("\"0x0102030\"").unwrap_err(),
+            "invalid length 7, expected an even number of hex digits at line 1 column 11"
+        );
+
+        // Unexpected value type
+        assert_eq!(
+            hr_deserialize::<CowStruct<'_>>("1").unwrap_err(),
+            "invalid type: integer `1`, expected a bytestring at line 1 column 1"
+        );
+
+        // A plain slice cannot be created from decoded bytes
+        assert_eq!(
+            hr_deserialize::<BorrowedBytesStruct<'_>>(&val_str).unwrap_err(),
+            instantiation_message(
+                "&[u8]",
+                concat![
+                    "from a byte slice of length 4: ",
+                    "the bytes cannot be borrowed from the input; use `Cow<[u8]>` instead ",
+                    "at line 1 column 12"
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn borrowed_bytes_visitor_binary() {
+        // Normal operation; the bytes are borrowed from the input
+        let val = CowStruct(Cow::Borrowed(&[1, 2, 3, 4]));
+        let val_bytes = bin_serialize(&val).unwrap();
+        let val_back = bin_deserialize::<CowStruct<'_>>(&val_bytes).unwrap();
+        assert!(matches!(val_back.0, Cow::Borrowed(_)));
+        assert_eq!(val, val_back);
+
+        let val = BorrowedBytesStruct(&[1, 2, 3, 4]);
+        let val_bytes = bin_serialize(&val).unwrap();
+        let val_back = bin_deserialize::<BorrowedBytesStruct<'_>>(&val_bytes).unwrap();
+        assert_eq!(val, val_back);
+
+        // Unexpected value type
+        let wrong_val_bytes = bin_serialize(WrongValue(0x01020304)).unwrap();
+        assert_eq!(
+            bin_deserialize::<CowStruct<'_>>(&wrong_val_bytes).unwrap_err(),
+            "invalid type: integer `16909060`, expected a bytestring"
+        );
+
+        // The input cannot be borrowed from when reading from a stream
+        let mut deserializer = rmp_serde::Deserializer::new(val_bytes.as_slice());
+        let val_back = CowStruct::deserialize(&mut deserializer).unwrap();
+        assert!(matches!(val_back.0, Cow::Owned(_)));
+        let mut deserializer = rmp_serde::Deserializer::new(val_bytes.as_slice());
+        assert_eq!(
+            BorrowedBytesStruct::deserialize(&mut deserializer)
+                .unwrap_err()
+                .to_string(),
+            instantiation_message(
+                "&[u8]",
+                concat![
+                    "from a byte slice of length 4: ",
+                    "the bytes cannot be borrowed from the input; use `Cow<[u8]>` instead"
+                ]
+            )
         );
     }
 
@@ -499,11 +1250,13 @@ mod tests {
         let bad_struct_bytes = bin_serialize(BadSliceStruct(BadType([1, 2, 3, 4]))).unwrap();
         assert_eq!(
             bin_deserialize::<BadSliceStruct>(&bad_struct_bytes).unwrap_err(),
-            concat![
-                "Failed to instantiate `serde_encoded_bytes::low_level::tests::BadType` ",
-                "from a byte slice of length 4: ",
-                "BadType cannot deserialize from `&[u8]` of length 4"
-            ]
+            instantiation_message(
+                "serde_encoded_bytes::low_level::tests::BadType",
+                concat![
+                    "from a byte slice of length 4: ",
+                    "BadType cannot deserialize from `&[u8]` of length 4"
+                ]
+            )
         );
     }
 
@@ -519,7 +1272,7 @@ mod tests {
         // Failed to decode
         assert_eq!(
             hr_deserialize::<BorrowStruct<4>>("\"0x0102030\"").unwrap_err(),
-            "Odd number of digits at line 1 column 11"
+            "invalid length 7, expected an even number of hex digits at line 1 column 11"
         );
 
         // Unexpected value type
@@ -532,11 +1285,13 @@ mod tests {
         let bad_struct_str = hr_serialize(BadBorrowSliceStruct(BadType([1, 2, 3, 4]))).unwrap();
         assert_eq!(
             hr_deserialize::<BadBorrowSliceStruct>(&bad_struct_str).unwrap_err(),
-            concat![
-                "Failed to instantiate `serde_encoded_bytes::low_level::tests::BadType` ",
-                "from a byte slice of length 4: ",
-                "BadType cannot deserialize from `&[u8]` of length 4 at line 1 column 12"
-            ]
+            instantiation_message(
+                "serde_encoded_bytes::low_level::tests::BadType",
+                concat![
+                    "from a byte slice of length 4: ",
+                    "BadType cannot deserialize from `&[u8]` of length 4 at line 1 column 12"
+                ]
+            )
         );
     }
 
@@ -560,11 +1315,13 @@ mod tests {
         let bad_struct_bytes = bin_serialize(BadBorrowSliceStruct(BadType([1, 2, 3, 4]))).unwrap();
         assert_eq!(
             bin_deserialize::<BadBorrowSliceStruct>(&bad_struct_bytes).unwrap_err(),
-            concat![
-                "Failed to instantiate `serde_encoded_bytes::low_level::tests::BadType` ",
-                "from a byte slice of length 4: ",
-                "BadType cannot deserialize from `&[u8]` of length 4"
-            ]
+            instantiation_message(
+                "serde_encoded_bytes::low_level::tests::BadType",
+                concat![
+                    "from a byte slice of length 4: ",
+                    "BadType cannot deserialize from `&[u8]` of length 4"
+                ]
+            )
         );
     }
 
@@ -583,7 +1340,7 @@ mod tests {
         // Failed to decode
         assert_eq!(
             hr_deserialize::<GenericArray014Struct<U4>>("\"0x0102030\"").unwrap_err(),
-            "Odd number of digits at line 1 column 11"
+            "invalid length 7, expected an even number of hex digits at line 1 column 11"
         );
 
         // Unexpected value type
@@ -626,4 +1383,61 @@ mod tests {
             "Expected a bytestring of length 4, got 3"
         );
     }
+
+    #[test]
+    fn runtime_encoding() {
+        use super::{deserialize_array_with_encoding, serialize_with_encoding};
+        use crate::EncodingKind;
+
+        let bytes = [1u8, 0xf2, 3, 0xf4];
+
+        for (encoding, expected) in [
+            (EncodingKind::Hex, "\"0x01f203f4\""),
+            (EncodingKind::HexNoPrefixUpper, "\"01F203F4\""),
+        ] {
+            let mut val_str = Vec::new();
+            serialize_with_encoding(
+                &encoding,
+                &bytes,
+                &mut serde_json::Serializer::new(&mut val_str),
+            )
+            .unwrap();
+            assert_eq!(val_str, expected.as_bytes());
+
+            let mut deserializer = serde_json::Deserializer::from_str(expected);
+            let val_back: [u8; 4] =
+                deserialize_array_with_encoding(&encoding, &mut deserializer).unwrap();
+            assert_eq!(val_back, bytes);
+
+            let mut deserializer = serde_json::Deserializer::from_str(expected);
+            let val_back: Vec<u8> =
+                super::deserialize_slice_with_encoding(&encoding, &mut deserializer).unwrap();
+            assert_eq!(val_back, bytes);
+        }
+
+        // Binary formats are not affected
+        let mut val_bytes = Vec::new();
+        serialize_with_encoding(
+            &EncodingKind::Hex,
+            &bytes,
+            &mut rmp_serde::Serializer::new(&mut val_bytes),
+        )
+        .unwrap();
+        assert_eq!(val_bytes, [0xc4, 4, 1, 0xf2, 3, 0xf4]);
+        let val_back: [u8; 4] = deserialize_array_with_encoding(
+            &EncodingKind::Hex,
+            &mut rmp_serde::Deserializer::new(val_bytes.as_slice()),
+        )
+        .unwrap();
+        assert_eq!(val_back, bytes);
+
+        // Encodings as values can be used as well
+        let mut deserializer = serde_json::Deserializer::from_str("\"0x010203\"");
+        assert_eq!(
+            deserialize_array_with_encoding::<[u8; 4], _, _, 4>(&Hex, &mut deserializer)
+                .unwrap_err()
+                .to_string(),
+            "Expected a bytestring of length 4, got 3 at line 1 column 10"
+        );
+    }
 }
@@ -0,0 +1,172 @@
+//! Implementations of `serde_with` traits for the containers,
+//! making them usable in `#[serde_as(as = "...")]` annotations.
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec::Vec};
+use core::array::TryFromSliceError;
+
+use serde::{Deserializer, Serializer};
+use serde_with::{DeserializeAs, SerializeAs};
+
+#[cfg(feature = "alloc")]
+use crate::containers::BoxedArrayLike;
+#[cfg(feature = "generic-array-014")]
+use crate::containers::GenericArray014;
+use crate::containers::{ArrayLike, BorrowedSliceLike, SliceLike};
+use crate::encoding::Encoding;
+
+impl<Enc: Encoding, T: AsRef<[u8]>> SerializeAs<T> for ArrayLike<Enc> {
+    fn serialize_as<S: Serializer>(source: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        Self::serialize(source, serializer)
+    }
+}
+
+impl<'de, Enc: Encoding, const N: usize> DeserializeAs<'de, [u8; N]> for ArrayLike<Enc> {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<[u8; N], D::Error> {
+        Self::deserialize(deserializer)
+    }
+}
+
+impl<Enc: Encoding, T: AsRef<[u8]>> SerializeAs<T> for SliceLike<Enc> {
+    fn serialize_as<S: Serializer>(source: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        Self::serialize(source, serializer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, Enc: Encoding> DeserializeAs<'de, Vec<u8>> for SliceLike<Enc> {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        Self::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, Enc: Encoding> DeserializeAs<'de, Box<[u8]>> for SliceLike<Enc> {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<Box<[u8]>, D::Error> {
+        Self::deserialize(deserializer)
+    }
+}
+
+impl<Enc: Encoding, T: AsRef<[u8]>> SerializeAs<T> for BorrowedSliceLike<Enc> {
+    fn serialize_as<S: Serializer>(source: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        Self::serialize(source, serializer)
+    }
+}
+
+impl<'de, Enc: Encoding, const N: usize> DeserializeAs<'de, [u8; N]> for BorrowedSliceLike<Enc> {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<[u8; N], D::Error> {
+        Self::deserialize::<_, TryFromSliceError, _>(deserializer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Enc: Encoding, const N: usize> SerializeAs<Box<[u8; N]>> for BoxedArrayLike<Enc> {
+    fn serialize_as<S: Serializer>(
+        source: &Box<[u8; N]>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Self::serialize(source, serializer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, Enc: Encoding, const N: usize> DeserializeAs<'de, Box<[u8; N]>> for BoxedArrayLike<Enc> {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<Box<[u8; N]>, D::Error> {
+        Self::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "generic-array-014")]
+impl<Enc, L> SerializeAs<generic_array_014::GenericArray<u8, L>> for GenericArray014<Enc>
+where
+    Enc: Encoding,
+    L: generic_array_014::ArrayLength<u8>,
+{
+    fn serialize_as<S: Serializer>(
+        source: &generic_array_014::GenericArray<u8, L>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Self::serialize(source, serializer)
+    }
+}
+
+#[cfg(feature = "generic-array-014")]
+impl<'de, Enc, L> DeserializeAs<'de, generic_array_014::GenericArray<u8, L>>
+    for GenericArray014<Enc>
+where
+    Enc: Encoding,
+    L: generic_array_014::ArrayLength<u8>,
+{
+    fn deserialize_as<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<generic_array_014::GenericArray<u8, L>, D::Error> {
+        Self::deserialize(deserializer)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::{boxed::Box, collections::BTreeMap, vec, vec::Vec};
+
+    use serde::{Deserialize, Serialize};
+    use serde_with::serde_as;
+
+    use crate::{
+        tests::common::{bin_deserialize, bin_serialize, hr_deserialize, hr_serialize},
+        ArrayLike, BorrowedSliceLike, BoxedArrayLike, Hex, SliceLike,
+    };
+
+    #[serde_as]
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Nested {
+        #[serde_as(as = "Option<ArrayLike<Hex>>")]
+        maybe_array: Option<[u8; 2]>,
+        #[serde_as(as = "Vec<SliceLike<Hex>>")]
+        slices: Vec<Vec<u8>>,
+        #[serde_as(as = "BTreeMap<ArrayLike<Hex>, BoxedArrayLike<Hex>>")]
+        by_key: BTreeMap<[u8; 2], Box<[u8; 3]>>,
+        #[serde_as(as = "BorrowedSliceLike<Hex>")]
+        borrowed: [u8; 1],
+    }
+
+    #[test]
+    fn roundtrip() {
+        let val = Nested {
+            maybe_array: Some([1, 2]),
+            slices: vec![vec![3, 4, 5], vec![]],
+            by_key: [([6, 7], Box::new([8, 9, 10]))].into(),
+            borrowed: [11],
+        };
+
+        let val_str = hr_serialize(&val).unwrap();
+        assert_eq!(
+            val_str,
+            concat![
+                "{\"maybe_array\":\"0x0102\",\"slices\":[\"0x030405\",\"0x\"],",
+                "\"by_key\":{\"0x0607\":\"0x08090a\"},\"borrowed\":\"0x0b\"}"
+            ]
+        );
+        assert_eq!(hr_deserialize::<Nested>(&val_str).unwrap(), val);
+
+        let val_bytes = bin_serialize(&val).unwrap();
+        assert_eq!(bin_deserialize::<Nested>(&val_bytes).unwrap(), val);
+    }
+
+    #[test]
+    fn missing_option() {
+        let val = hr_deserialize::<Nested>("{\"slices\":[],\"by_key\":{},\"borrowed\":\"0x0b\"}")
+            .unwrap();
+        assert_eq!(val.maybe_array, None);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            hr_deserialize::<Nested>(
+                "{\"maybe_array\":\"0x010203\",\"slices\":[],\"by_key\":{},\"borrowed\":\"0x0b\"}"
+            )
+            .unwrap_err(),
+            "Expected a bytestring of length 2, got 3 at line 1 column 25"
+        );
+    }
+}
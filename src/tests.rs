@@ -2,11 +2,19 @@
 
 #![allow(clippy::unwrap_used)]
 
+#[cfg(feature = "alloc")]
 mod asn1;
+#[cfg(feature = "alloc")]
 mod bincode;
+#[cfg(feature = "alloc")]
 mod cbor;
-mod common;
+pub(crate) mod common;
+#[cfg(feature = "alloc")]
 mod messagepack;
+mod no_alloc;
+#[cfg(feature = "alloc")]
 mod serde_json;
+#[cfg(feature = "alloc")]
 mod serde_json_core;
+#[cfg(feature = "alloc")]
 mod toml;
@@ -66,3 +66,52 @@ fn roundtrip_slice() {
     let val_back = bincode_deserialize::<TestSlice>(&val_bytes).unwrap();
     assert_eq!(val, val_back);
 }
+
+#[test]
+fn roundtrip_array_tuple() {
+    use crate::{ArrayTupleLike, Hex};
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct TestArrayTuple {
+        #[serde(with = "ArrayTupleLike::<Hex>")]
+        value: [u8; 16],
+    }
+
+    let val = TestArrayTuple {
+        value: EXAMPLE_BYTES,
+    };
+    let val_bytes = bincode_serialize(&val).unwrap();
+    // Exactly the bytes, without the length prefix
+    assert_eq!(val_bytes, EXAMPLE_BYTES);
+
+    let val_back = bincode_deserialize::<TestArrayTuple>(&val_bytes).unwrap();
+    assert_eq!(val, val_back);
+
+    assert_eq!(
+        bincode_deserialize::<TestArrayTuple>(val_bytes.get(..15).unwrap()).unwrap_err(),
+        "UnexpectedEnd { additional: 1 }"
+    );
+}
+
+#[test]
+fn roundtrip_borrowed_bytes() {
+    use alloc::borrow::Cow;
+
+    use crate::{BorrowedBytes, Hex};
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct TestCow<'a> {
+        #[serde(borrow, with = "BorrowedBytes::<Hex>")]
+        value: Cow<'a, [u8]>,
+    }
+
+    let val = TestCow {
+        value: Cow::Borrowed(&EXAMPLE_BYTES),
+    };
+    let val_bytes = bincode_serialize(&val).unwrap();
+    assert_eq!(val_bytes, BINCODE_REF);
+
+    let val_back = bincode_deserialize::<TestCow<'_>>(&val_bytes).unwrap();
+    assert!(matches!(val_back.value, Cow::Borrowed(_)));
+    assert_eq!(val, val_back);
+}
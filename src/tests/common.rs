@@ -1,3 +1,9 @@
+#[cfg(feature = "alloc")]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
 use serde::{Deserialize, Serialize};
 
 use crate::{ArrayLike, Hex, SliceLike};
@@ -8,12 +14,14 @@ pub(crate) struct TestArray {
     pub(crate) value: [u8; 16],
 }
 
+#[cfg(feature = "alloc")]
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) struct BiggerTestArray {
     #[serde(with = "ArrayLike::<Hex>")]
     pub(crate) value: [u8; 17],
 }
 
+#[cfg(feature = "alloc")]
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) struct SmallerTestArray {
     #[serde(with = "ArrayLike::<Hex>")]
@@ -30,10 +38,43 @@ pub(crate) const EXAMPLE_BYTES: [u8; 16] = [
     0, 1, 0xf2, 3, 0xf4, 5, 0xf6, 7, 0xf8, 9, 0xfa, 11, 0xfc, 13, 14, 0xff,
 ];
 
+#[cfg(feature = "alloc")]
 pub(crate) const BIGGER_EXAMPLE_BYTES: [u8; 17] = [
     0, 1, 0xf2, 3, 0xf4, 5, 0xf6, 7, 0xf8, 9, 0xfa, 11, 0xfc, 13, 14, 0xff, 0xfe,
 ];
 
+#[cfg(feature = "alloc")]
 pub(crate) const SMALLER_EXAMPLE_BYTES: [u8; 15] = [
     1, 0xf2, 3, 0xf4, 5, 0xf6, 7, 0xf8, 9, 0xfa, 11, 0xfc, 13, 14, 0xff,
 ];
+
+/// Returns the expected message for a failed conversion into the type `type_name`,
+/// depending on whether the `hide-type-names` feature is enabled.
+#[cfg(feature = "alloc")]
+pub(crate) fn instantiation_message(type_name: &str, details: &str) -> String {
+    if cfg!(feature = "hide-type-names") {
+        ["Failed to instantiate the target type ", details].concat()
+    } else {
+        ["Failed to instantiate `", type_name, "` ", details].concat()
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn hr_serialize<T: Serialize>(value: T) -> Result<String, String> {
+    serde_json::to_string(&value).map_err(|err| err.to_string())
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn hr_deserialize<'de, T: Deserialize<'de>>(string: &'de str) -> Result<T, String> {
+    serde_json::from_str::<T>(string).map_err(|err| err.to_string())
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn bin_serialize<T: Serialize>(value: T) -> Result<Vec<u8>, String> {
+    rmp_serde::encode::to_vec(&value).map_err(|err| err.to_string())
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn bin_deserialize<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> Result<T, String> {
+    rmp_serde::decode::from_slice(bytes).map_err(|err| err.to_string())
+}
@@ -0,0 +1,86 @@
+//! Checks that the array containers can be used with `serde-json-core`
+//! without touching the allocator.
+
+use super::common::{TestArray, TestSlice, EXAMPLE_BYTES};
+
+const JSON_REF: &[u8; 46] = b"{\"value\":\"0x0001f203f405f607f809fa0bfc0d0eff\"}";
+
+#[test]
+fn serialize_into_fixed_buffer() {
+    let mut buffer = [0u8; 64];
+
+    let val = TestArray {
+        value: EXAMPLE_BYTES,
+    };
+    let len = serde_json_core::to_slice(&val, &mut buffer).unwrap();
+    assert_eq!(buffer.get(..len).unwrap(), JSON_REF);
+
+    let val = TestSlice {
+        value: EXAMPLE_BYTES,
+    };
+    let len = serde_json_core::to_slice(&val, &mut buffer).unwrap();
+    assert_eq!(buffer.get(..len).unwrap(), JSON_REF);
+}
+
+#[test]
+fn serialize_into_short_buffer() {
+    // The encoded string is written directly into the serializer's output,
+    // so running out of space is reported as a serialization error.
+    let mut buffer = [0u8; 32];
+    let val = TestArray {
+        value: EXAMPLE_BYTES,
+    };
+    assert!(serde_json_core::to_slice(&val, &mut buffer).is_err());
+}
+
+#[test]
+fn deserialize_array() {
+    let (val, _size) = serde_json_core::from_slice::<TestArray>(JSON_REF).unwrap();
+    assert_eq!(val.value, EXAMPLE_BYTES);
+}
+
+#[cfg(not(feature = "alloc"))]
+#[test]
+fn deserialize_slice_requires_alloc() {
+    assert!(serde_json_core::from_slice::<TestSlice>(JSON_REF).is_err());
+}
+
+#[cfg(feature = "scoped")]
+#[test]
+fn scoped_encoding() {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{with_encoding, ArrayLike, EncodingKind, Scoped};
+
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct ScopedArray {
+        #[serde(with = "ArrayLike::<Scoped>")]
+        value: [u8; 16],
+    }
+
+    const JSON_NO_PREFIX: &[u8; 44] = b"{\"value\":\"0001f203f405f607f809fa0bfc0d0eff\"}";
+
+    // Without `std` the selected encoding is global, so the tests using it must not overlap.
+    critical_section::with(|_| {
+        let val = ScopedArray {
+            value: EXAMPLE_BYTES,
+        };
+        let mut buffer = [0u8; 64];
+
+        let len = with_encoding(EncodingKind::HexNoPrefix, || {
+            serde_json_core::to_slice(&val, &mut buffer)
+        })
+        .unwrap();
+        assert_eq!(buffer.get(..len).unwrap(), JSON_NO_PREFIX);
+
+        let (val_back, _size) = with_encoding(EncodingKind::HexNoPrefix, || {
+            serde_json_core::from_slice::<ScopedArray>(JSON_NO_PREFIX)
+        })
+        .unwrap();
+        assert_eq!(val_back, val);
+
+        // No encoding is selected outside of `with_encoding()`
+        // (not checked on serialization, since `serde-json-core` panics on custom errors)
+        assert!(serde_json_core::from_slice::<ScopedArray>(JSON_NO_PREFIX).is_err());
+    });
+}
@@ -2,8 +2,9 @@ use alloc::string::{String, ToString};
 
 use serde::{de::DeserializeOwned, Serialize};
 
+use super::common::TestSlice;
 use super::common::{
-    BiggerTestArray, SmallerTestArray, TestArray, TestSlice, BIGGER_EXAMPLE_BYTES, EXAMPLE_BYTES,
+    BiggerTestArray, SmallerTestArray, TestArray, BIGGER_EXAMPLE_BYTES, EXAMPLE_BYTES,
     SMALLER_EXAMPLE_BYTES,
 };
 